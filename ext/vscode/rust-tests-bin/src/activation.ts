@@ -280,8 +280,13 @@ export function registerCodeLens() {
 
 				if(textEditor){
 					textEditor.edit( builder => {
-						if(typeof args[1] === typeof vscode.Position)	// We insert since no "" defined.
-							{builder.insert(<vscode.Position>args[1], "\"" + filename + "\"");}
+						const position = args[1];
+						if(position instanceof vscode.Position) {	// We insert since no "" defined.
+							// Separate from named parameters that follow, like `cfg(...)`
+							let next = textEditor.document.lineAt(position.line).text.substring(position.character).trim();
+							let separator = (next.startsWith(")") || next.startsWith("}")) ? "" : ", ";
+							builder.insert(position, "\"" + filename + "\"" + separator);
+						}
 						else	// Replace content in ""
 							{builder.replace(<vscode.Range>args[1], "\"" + filename + "\"");}
 					});
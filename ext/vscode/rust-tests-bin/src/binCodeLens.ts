@@ -12,7 +12,7 @@
  */
 
 import * as vscode from 'vscode';
-import { isFileInBin } from './fileSystem';
+import { isFileInBin, TESTS_BIN_CONVENTION } from './fileSystem';
 import * as parser from './parser';
 
 /**
@@ -32,8 +32,9 @@ export class TestsBinCodeLensProvider implements vscode.CodeLensProvider {
 	public readonly onDidChangeCodeLenses: vscode.Event<void> = this._onDidChangeCodeLenses.event;
 
 	constructor() {
-		// This regex detect #[unit_tests()] attribute and unit__tests! (), {} proc macro.
-        this.macroRegex = /#\[unit_tests\([^)]*\)\]|unit__tests!\([^)]*\)|unit__tests!\{[^}]*\}/g;
+		// This regex detect #[unit_tests()] and #[unit_tests] attributes and unit__tests! (), {} proc macro, with parameters
+		// nested up to 3 levels like `cfg(all(test, any(unix, windows)))`.
+        this.macroRegex = /#\[unit_tests(?:\((?:[^()]|\((?:[^()]|\((?:[^()]|\([^()]*\))*\))*\))*\))?\]|unit__tests!\((?:[^()]|\((?:[^()]|\((?:[^()]|\([^()]*\))*\))*\))*\)|unit__tests!\{(?:[^{}()]|\((?:[^()]|\((?:[^()]|\([^()]*\))*\))*\))*\}/g;
 
 		vscode.workspace.onDidChangeConfiguration((_) => {
 			this._onDidChangeCodeLenses.fire();
@@ -61,10 +62,20 @@ export class TestsBinCodeLensProvider implements vscode.CodeLensProvider {
 			while ((matches = this.macroRegex.exec(text)) !== null) {
 
 				if(!this.isCommented(matches.index, comments)){	// Make sure match isn't commented
-					let param = parser.getFilenameRangePosition(document, matches);	// Get macro parameters
 					let range = parser.getMatchRange(document, matches);	// Get macro range for codelens
 
-					if(typeof param[1] === typeof vscode.Position) {	// Show create file since filename parameter not found
+					if(matches[0] === "#[unit_tests]") {	// Path derived from item with convention
+						let filename = parser.getDerivedFilename(document, matches, TESTS_BIN_CONVENTION);
+						if(filename !== undefined && isFileInBin(filename))
+							{this.createCodelensToOpenFile(range, [filename, range]);}	// Only open since there is no path to rewrite
+						continue;
+					}
+
+					let param = parser.getFilenameRangePosition(document, matches);	// Get macro parameters
+
+					if(param === undefined) {	// Path isn't a single file, like `dir` or glob patterns
+						continue;
+					} else if(param[1] instanceof vscode.Position) {	// Show create file since filename parameter not found
 						this.createCodelensToCreateFile(range, param);
 					} else {
 						if(isFileInBin(param[0])) 
@@ -88,12 +99,7 @@ export class TestsBinCodeLensProvider implements vscode.CodeLensProvider {
 	private createCodelensForExistingFile(range: vscode.Range, param: [string, vscode.Range | vscode.Position]) {
 
 		// Open file codelens
-		this.codeLenses.push(new vscode.CodeLens(range, {
-			title: this.getShortcutTitle("folder-opened", "Open file"),
-			tooltip: "Open unit tests file from bin.",
-			command: "rust-tests-bin.open",
-			arguments: [param]
-		}));
+		this.createCodelensToOpenFile(range, param);
 
 		if(vscode.workspace.getConfiguration('rust-tests-bin').get<boolean>('display.showRenameFile')){	// Only if enabled
 			// Rename file codelens
@@ -116,6 +122,22 @@ export class TestsBinCodeLensProvider implements vscode.CodeLensProvider {
 		}
 	}
 
+	/**
+	 * Create the codeLens Open file
+	 * @param range Range that codeLens apply to
+	 * @param param Macro parameters.
+	 */
+	private createCodelensToOpenFile(range: vscode.Range, param: [string, vscode.Range | vscode.Position]) {
+
+		this.codeLenses.push(new vscode.CodeLens(range, {
+			title: this.getShortcutTitle("folder-opened", "Open file"),
+			tooltip: "Open unit tests file from bin.",
+			command: "rust-tests-bin.open",
+			arguments: [param]
+		}));
+
+	}
+
 	/**
 	 * Create the codeLens Create file
 	 * @param range Range that codeLens apply to
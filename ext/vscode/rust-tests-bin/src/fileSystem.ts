@@ -14,14 +14,29 @@ import * as fs from 'fs';
 // `tests_bin` folder.
 export let TESTS_BIN_FOLDER = "";
 
+// Path convention of `#[unit_tests]` without path.
+export let TESTS_BIN_CONVENTION = "";
+
 // Contants
 const DEFAULT_BIN_FOLDER = "tests/unit";        // Contains the default `tests_bin folder path`.
-const CONFIG_FOLDER_KEY = "tests_bin-folder";   // Key used to fetch custom folder in config.toml
+const DEFAULT_CONVENTION = "{ident}.rs";        // Contains the default path convention.
+const CONFIG_KEY_PREFIX = "tests_bin-";         // Prefix of keys in .cargo/config.toml [env]
+const FOLDER_KEY = "folder";                    // Key used to fetch custom folder
+const CONVENTION_KEY = "convention";            // Key used to fetch custom path convention
+const SETTINGS_FILE = "tests_bin.toml";         // Settings file next to Cargo.toml
+const PACKAGE_METADATA_TABLE = "package.metadata.tests_bin";     // Table of settings in Cargo.toml
+const WORKSPACE_METADATA_TABLE = "workspace.metadata.tests_bin"; // Table of workspace settings in Cargo.toml
+const WORKSPACE_PLACEHOLDER = "{workspace}";    // Placeholder replaced by workspace root folder
+const CRATE_PLACEHOLDER = "{crate}";            // Placeholder replaced by package name
 
 // Regexes to pull value from .cargo/config.toml
 const CONFIG_INNER_REGEX = /value = "[^"]*"/;
 const CONFIG_VALUE_REGEX = /\"(.*)\"/i;
 
+// Regexes to pull table header and `key = "value"` lines from toml files
+const TOML_HEADER_REGEX = /^\[\s*([^\[\]]*?)\s*\]/;
+const TOML_VALUE_REGEX = /^([\w-]+)\s*=\s*"([^"]*)"/;
+
 // Regex for file name validation. Ref : https://digitalfortress.tech/tips/top-15-commonly-used-regex/
 const FILE_NAME_VALIDATION_REGEX = /^[\w,\s-\/]+\.rs$/;
 
@@ -333,14 +348,15 @@ export function isCrateAdded() : boolean {
 }
 
 /**
- * Refresh / Initialize `tests_bin_folder` global variable.
+ * Refresh / Initialize `tests_bin_folder` and `tests_bin_convention` global variables.
  */
 export function refreshTestsBinFolder() {
     TESTS_BIN_FOLDER = fetchTestsBinFolder() + "/";
+    TESTS_BIN_CONVENTION = fetchSetting(CONVENTION_KEY) ?? DEFAULT_CONVENTION;
 }
 
 /**
- * Fetch the tests_bin folder in .cargo/config.toml if exists.
+ * Fetch the tests_bin folder from settings if exists.
  * 
  * Put the result in `tests_bin_folder` global
  */
@@ -349,35 +365,122 @@ function fetchTestsBinFolder() : string {
 	if(vscode.workspace.workspaceFolders !== undefined) {
 		
         let projectPath = vscode.workspace.workspaceFolders[0].uri.fsPath + "/";   // Project path
-		let filePath = projectPath + '.cargo/config.toml';    // config.toml path.
 
-		// Verify if file exists before reading.
-		if(fs.existsSync(filePath)){
-			// Get data sync from file
-			const data = fs.readFileSync(filePath, 'utf-8');
-
-            // Read config.toml line by line
-            let lines = data.split(/\r?\n/);
-            for(let i = 0; i < lines.length; i++){
-                // Match line start with key
-                if(lines[i].trim().substring(0, CONFIG_FOLDER_KEY.length) === CONFIG_FOLDER_KEY){
-                    // Get segment between curly braces.
-                    let inner = CONFIG_INNER_REGEX.exec(lines[i]);
-                    if(inner !== null){
-                        // Get folder value
-                        let value = CONFIG_VALUE_REGEX.exec(inner[0].toString());
-
-                        if(value !== null){
-                            return projectPath + value[0].toString().replace(/"/g, "");	// Return value fetched from config.toml
-                        }
-                    }
+        return projectPath + (fetchSetting(FOLDER_KEY) ?? DEFAULT_BIN_FOLDER);
+	}
+
+    throw new Error("Cannot initialize `tests_bin` folder!");
+
+}
+
+/**
+ * Fetch a setting with the same precedence as the crate : env keys of `.cargo/config.toml`, then `tests_bin.toml`,
+ * then `[package.metadata.tests_bin]` and `[workspace.metadata.tests_bin]` of `Cargo.toml`.
+ * 
+ * `{workspace}` is replaced by `.` and `{crate}` by the package name, since the opened folder is the project folder.
+ * @param key Key of setting without `tests_bin-` prefix, like `folder`
+ * @returns Value of setting or undefined if not found
+ */
+function fetchSetting(key : string) : string | undefined {
+
+    if(vscode.workspace.workspaceFolders === undefined)
+        {return undefined;}
+
+    let projectPath = vscode.workspace.workspaceFolders[0].uri.fsPath + "/";   // Project path
+    let cargo = readFileIfExists(projectPath + 'Cargo.toml');
+
+    let value = fetchConfigValue(readFileIfExists(projectPath + '.cargo/config.toml'), CONFIG_KEY_PREFIX + key)
+        ?? fetchTableValue(readFileIfExists(projectPath + SETTINGS_FILE), "", key)
+        ?? fetchTableValue(cargo, PACKAGE_METADATA_TABLE, key)
+        ?? fetchTableValue(cargo, WORKSPACE_METADATA_TABLE, key);
+
+    if(value !== undefined) {
+        let name = fetchTableValue(cargo, "package", "name") ?? "";
+        value = value.split(WORKSPACE_PLACEHOLDER).join(".").split(CRATE_PLACEHOLDER).join(name);
+    }
+
+    return value;
+
+}
+
+/**
+ * Read file content if it exists.
+ * @param filePath Path of file to read
+ * @returns Content of file or undefined if it doesn't exist
+ */
+function readFileIfExists(filePath : string) : string | undefined {
+
+    if(fs.existsSync(filePath))
+        {return fs.readFileSync(filePath, 'utf-8');}
+
+    return undefined;
+}
+
+/**
+ * Fetch an env key value of .cargo/config.toml, like `tests_bin-folder = { value = "tests/unit", relative = true }`
+ * or `tests_bin-folder = "tests/unit"`.
+ * @param data Content of config.toml
+ * @param key Key to fetch
+ * @returns Value of key or undefined if not found
+ */
+function fetchConfigValue(data : string | undefined, key : string) : string | undefined {
+
+    if(data === undefined)
+        {return undefined;}
+
+    // Read config.toml line by line
+    let lines = data.split(/\r?\n/);
+    for(let i = 0; i < lines.length; i++){
+        let line = lines[i].trim();
+
+        // Match line start with key followed by `=`
+        if(line.substring(0, key.length) === key && line.substring(key.length).trim().startsWith("=")){
+            // Get segment between curly braces, or the plain string value.
+            let inner = CONFIG_INNER_REGEX.exec(line) ?? CONFIG_VALUE_REGEX.exec(line.substring(key.length));
+            if(inner !== null){
+                // Get value
+                let value = CONFIG_VALUE_REGEX.exec(inner[0].toString());
+
+                if(value !== null){
+                    return value[0].toString().replace(/"/g, "");	// Return value fetched from config.toml
                 }
             }
-		}
+        }
+    }
 
-        return projectPath + DEFAULT_BIN_FOLDER;	// Return default bin folder value
-	}
+    return undefined;
+}
 
-    throw new Error("Cannot initialize `tests_bin` folder!");
+/**
+ * Fetch a `key = "value"` line of a toml table. Root table is `""`.
+ * @param data Content of toml file
+ * @param table Table header like `package.metadata.tests_bin`
+ * @param key Key to fetch
+ * @returns Value of key or undefined if not found
+ */
+function fetchTableValue(data : string | undefined, table : string, key : string) : string | undefined {
+
+    if(data === undefined)
+        {return undefined;}
+
+    let current = "";   // Root table before first header
+
+    // Read toml line by line
+    let lines = data.split(/\r?\n/);
+    for(let i = 0; i < lines.length; i++){
+        let line = lines[i].trim();
+
+        if(line.startsWith("[[")) {
+            current = "[[";     // Arrays of tables aren't read
+        } else if(line.startsWith("[")) {
+            let header = TOML_HEADER_REGEX.exec(line);
+            current = header !== null ? header[1].replace(/\s/g, "") : "[";
+        } else if(current === table) {
+            let value = TOML_VALUE_REGEX.exec(line);
+            if(value !== null && value[1] === key)
+                {return value[2];}
+        }
+    }
 
-}
\ No newline at end of file
+    return undefined;
+}
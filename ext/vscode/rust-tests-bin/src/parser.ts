@@ -14,8 +14,8 @@ import * as vscode from 'vscode';
 // Ref : https://stackoverflow.com/questions/5989315/regex-for-match-replacing-javascript-comments-both-multiline-and-inline
 const commentRegex = /\/\*[\s\S]*?\*\/|([^\\:]|^)\/\/.*$/gm;
 
-// This extract each macro parameter.
-const parameterRegex = /\"(.*?)\"/gm; 
+// This extract kind and identifier of the item below an attribute, after other attributes and qualifiers.
+const itemRegex = /^\s*(?:#\[[^\]]*\]\s*)*(?:pub(?:\s*\([^)]*\))?\s+)?(?:(?:const|async|unsafe|default|extern(?:\s+"[^"]*")?)\s+)*(fn|struct|enum|union|trait|type|mod|const|static)\s+(?:mut\s+)?(?:r#)?(\w+)/;
 
 /**
  * Get document comments ranges in an array of tuples.
@@ -89,44 +89,137 @@ function getRangeFromStartEnd(document: vscode.TextDocument, start : number, end
 /**
  * Extract filename with range or position if no filename.
  * 
+ * The filename is the first positional string literal or the `path = "..."` named parameter. String literals
+ * of `name`, `cfg(...)`, `attrs(...)` and other parameters are skipped.
+ * 
  * @param match Match of regex
- * @returns Tuple of filename with it's range or position where to insert text.
+ * @returns Tuple of filename with it's range or position where to insert text, or undefined if the path
+ * isn't a string literal, like `concat!(...)`, or is a folder or a glob pattern.
  */
-export function getFilenameRangePosition(document: vscode.TextDocument, match : any) : [string, vscode.Range | vscode.Position]  {
-    // Reset regex
-    parameterRegex.lastIndex = 0;
+export function getFilenameRangePosition(document: vscode.TextDocument, match : any) : [string, vscode.Range | vscode.Position] | undefined  {
 
-    // Extract filename parameter via parameterRegex.
-    let param = parameterRegex.exec(match[0].toString());
+    const text = match[0].toString();
+    const param = getPathParameter(text);
 
-    if(param !== null) { // If filename was found, return filename and it's ranfe
-        return [param[0].toString().replace(/"/g, "").trim(), getRangeOfParameter(document, match, param)];
-    } else { // If no filename found, return position to insert new filename
+    if(param === undefined) {   // Path can't be linked to one file
+        return undefined;
+    } else if(param === null) { // If no filename found, return position to insert new filename
         return ["", getPositionOfParameter(document, match)];
+    } else if(/[*?]/.test(param[0])) {  // Glob patterns link many files
+        return undefined;
+    } else { // If filename was found, return filename and it's range including ""
+        const start = document.positionAt(match.index + param[1]);
+        const end = document.positionAt(match.index + param[2]);
+        return [param[0].trim(), new vscode.Range(start, end)];
     }
 }
 
 /**
- * Get the range of parameter including ""
- * @param document Document to get line from
- * @param match Macro match
- * @param param Parameter match
- * @returns Range of parameter including ""
+ * Find the path parameter of macro parameters.
+ * 
+ * @param text Macro text, like `unit__tests!("add.rs", cfg(any(test, bench)))`
+ * @returns Path with start and end index of literal, null if there is no path parameter or undefined if the path
+ * isn't a plain string literal or parameters link a folder.
  */
-function getRangeOfParameter(document: vscode.TextDocument, match : any, param : any) : vscode.Range {
-    // Get line of document of parameter
-    const line = document.lineAt(document.positionAt(match.index + param.index).line);
+function getPathParameter(text : string) : [string, number, number] | null | undefined {
+
+    let start = text.search(/[({]/);  // Parameters start after first enclosure
+    if(start < 0)
+        {return null;}
+
+    let depth = 0;          // Depth of enclosures inside parameters
+    let ident = "";         // Name of named parameter
+    let assign = false;     // If `=` was found after name
+    let positional = 0;     // Count of positional parameters
+
+    for(let i = start + 1; i < text.length; i++){
+        const c = text[i];
+
+        if(c === '(' || c === '[' || c === '{') {
+            depth++;
+        } else if(c === ')' || c === ']' || c === '}') {
+            if(depth === 0)
+                {break;}
+            depth--;
+        } else if(depth > 0) {
+            // Skip content of cfg(...), attrs(...), concat!(...)
+            if(c === '"')
+                {i = getStringEnd(text, i) - 1;}
+        } else if(c === ',') {
+            if(ident === "" && !assign)     // Parameter without name
+                {positional++;}
+            ident = "";
+            assign = false;
+        } else if(c === '=') {
+            assign = true;
+        } else if(c === '"' || (c === 'r' && /^r#*"/.test(text.substring(i)))) {
+            const end = getStringEnd(text, i);
+            const literal = text.substring(i, end);
+            const value = literal.replace(/^r?#*"/, "").replace(/"#*$/, "");
+
+            if((ident === "" && !assign && positional === 0) || (ident === "path" && assign)) {
+                return [value, i, end];
+            }
+            i = end - 1;
+        } else if(/[A-Za-z_]/.test(c)) {
+            const name = /^\w+/.exec(text.substring(i))![0];
+
+            if(assign || (ident === "" && text.substring(i + name.length).trimStart().startsWith("!"))) {
+                if(ident === "path" || (ident === "" && positional === 0))
+                    {return undefined;}     // Path built with a macro like `concat!(...)`
+            } else if(name === "dir") {
+                return undefined;           // Folder linked as module tree
+            } else {
+                ident = name;
+            }
+            i += name.length - 1;
+        }
+    }
 
-    // Get both position of ""
-    const indexOfFirst = line.text.indexOf("\"");
-    const indexOfSecond = line.text.indexOf("\"", indexOfFirst +1);
+    return null;
+}
 
-    // Create start and end position from indexes
-    const posStart = new vscode.Position(line.lineNumber, indexOfFirst);
-    const posEnd = new vscode.Position(line.lineNumber, indexOfSecond + 1);
+/**
+ * Get the index after the closing `"` of a string literal, raw or not.
+ * @param text Text containing literal
+ * @param index Index of `"` or `r` starting literal
+ * @returns Index after end of literal
+ */
+function getStringEnd(text : string, index : number) : number {
 
-    // Return new range
-    return new vscode.Range(posStart, posEnd);
+    if(text[index] === 'r') {   // Raw string closes with `"` followed by the same count of `#`
+        const hashes = /^r(#*)"/.exec(text.substring(index))![1];
+        const end = text.indexOf('"' + hashes, index + hashes.length + 2);
+        return end < 0 ? text.length : end + hashes.length + 1;
+    }
+
+    for(let i = index + 1; i < text.length; i++){
+        if(text[i] === '\\')
+            {i++;}
+        else if(text[i] === '"')
+            {return i + 1;}
+    }
+
+    return text.length;
+}
+
+/**
+ * Derive the filename of `#[unit_tests]` without path from the item below, with the path convention.
+ * 
+ * @param document Document to read item from
+ * @param match Match of regex
+ * @param convention Path convention with `{ident}` and `{kind}` placeholders
+ * @returns Derived filename or undefined if item isn't recognized
+ */
+export function getDerivedFilename(document: vscode.TextDocument, match : any, convention : string) : string | undefined {
+
+    const text = document.getText().substring(match.index + match[0].length);
+    const item = itemRegex.exec(text);
+
+    if(item === null)
+        {return undefined;}
+
+    return convention.split("{ident}").join(item[2]).split("{kind}").join(item[1]);
 }
 
 /**
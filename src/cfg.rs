@@ -0,0 +1,83 @@
+use proc_macro::{TokenStream, TokenTree, Span, Delimiter};
+
+// Contants
+const CFG_SEPARATOR : char = ',';                                   // Separator of predicates in `all` and `any`
+const CFG_ASSIGN : char = '=';                                      // Assignment of key-value option
+const CFG_LIST : [&str; 2] = ["all", "any"];                        // Predicates containing a list of predicates
+const CFG_NOT : &str = "not";                                       // Predicate containing a single predicate
+
+/// Validate a [configuration predicate](https://doc.rust-lang.org/reference/conditional-compilation.html) like
+/// `test`, `feature = "slow"`, `all(...)`, `any(...)` or `not(...)`.
+///
+/// Error(s)
+/// Returns the span of the first incorrect token, or `None` span if predicate is incomplete.
+pub(crate) fn validate_cfg(predicate: TokenStream) -> Result<(), Option<Span>> {
+
+    let tokens : Vec<TokenTree> = predicate.into_iter().collect();
+
+    match validate_cfg_tokens(&tokens)? {
+        0 => Err(None),
+        count if count == tokens.len() => Ok(()),
+        count => Err(Some(tokens[count].span())),
+    }
+
+}
+
+/// Validate a list of predicates separated by `,` with optional trailing separator.
+fn validate_cfg_list(list: TokenStream) -> Result<(), Option<Span>> {
+
+    let tokens : Vec<TokenTree> = list.into_iter().collect();
+    let mut index = 0;
+
+    while index < tokens.len() {
+        index += match validate_cfg_tokens(&tokens[index..])? {
+            0 => return Err(Some(tokens[index].span())),
+            count => count,
+        };
+
+        // Predicates must be separated
+        match tokens.get(index) {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == CFG_SEPARATOR => index += 1,
+            Some(token) => return Err(Some(token.span())),
+            None => {},
+        }
+    }
+
+    Ok(())
+
+}
+
+/// Validate the predicate at start of tokens and returns the count of tokens it uses.
+fn validate_cfg_tokens(tokens: &[TokenTree]) -> Result<usize, Option<Span>> {
+
+    let ident = match tokens.first() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        Some(token) => return Err(Some(token.span())),
+        None => return Ok(0),
+    };
+
+    match tokens.get(1) {
+        // `all(...)`, `any(...)` and `not(...)`
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            if CFG_LIST.contains(&ident.as_str()) {
+                validate_cfg_list(group.stream())?;
+            } else if ident == CFG_NOT {
+                validate_cfg(group.stream()).map_err(|span| span.or(Some(group.span())))?;
+            } else {
+                return Err(Some(tokens[0].span()));
+            }
+            Ok(2)
+        },
+
+        // `key = "value"`
+        Some(TokenTree::Punct(punct)) if punct.as_char() == CFG_ASSIGN => match tokens.get(2) {
+            Some(TokenTree::Literal(lit)) if lit.to_string().starts_with(['"', 'r']) => Ok(3),
+            Some(token) => Err(Some(token.span())),
+            None => Err(Some(punct.span())),
+        },
+
+        // Single option like `test`
+        _ => Ok(1),
+    }
+
+}
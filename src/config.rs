@@ -1,133 +1,1129 @@
-use proc_macro::TokenStream;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-use crate::errors::TestsBinErrors;
+use proc_macro::{TokenStream, TokenTree, Span, Delimiter, Group, Punct, Spacing};
+use tests_bin_settings::parse_table;
+
+use crate::errors::{TestsBinErrors, TestsBinError, TestsBinWarnings, TestsBinWarning};
+use crate::item::{parse_item_signature, is_supported_kind};
+use crate::cfg::validate_cfg;
+use crate::literal::{decode_string_literal, expand_string_macros};
+use crate::files::{suggest_files, is_glob_pattern, glob_files, list_files, match_mirrors, relative_path, is_lexically_inside, is_canonically_inside};
+use crate::settings::{get_setting, get_settings_error, get_project_root, get_project_root_outside_cargo, get_invoking_file, get_module_folder};
+use crate::tracking::{track_file, track_folder};
+use crate::names::{generate_test_mod_name, generate_ident, is_valid_identifier, register_module_name};
 
 // Contants
 const TESTS_BIN_BASE_FOLDER : &str = "tests/unit";                  // Default tests bin base folder
 const TESTS_BIN_BASE_FOLDER_KEY : &str = "tests_bin-folder";        // Key used to fetch custom base folder
-const CARGO_MANIFEST_DIR : &str = "CARGO_MANIFEST_DIR";             // Cargo manifest directory key
-const ILLEGAL_CHARACTER_REPLACE : char = '_';                       // Illegal character will be replaceby this
+const TESTS_BIN_CONVENTION : &str = "{ident}.rs";                  // Default path convention of #[unit_tests] without path
+const TESTS_BIN_CONVENTION_KEY : &str = "tests_bin-convention";     // Key used to fetch custom path convention
+const CONVENTION_IDENT : &str = "{ident}";                          // Convention placeholder replaced by item identifier
+const CONVENTION_KIND : &str = "{kind}";                            // Convention placeholder replaced by item kind
 const PARAMETERS_SEPARATOR : char = ',';                            // Parameters separator.
+const PARAMETERS_ASSIGN : char = '=';                               // Parameters key and value assignment.
+const PATH_KEY : &str = "path";                                     // Key of path parameter.
+const NAME_KEY : &str = "name";                                     // Key of module name parameter.
+const DIR_KEY : &str = "dir";                                       // Key of folder linking parameter.
+const MODE_KEY : &str = "mode";                                     // Key of linking mode parameter.
+const ATTRS_KEY : &str = "attrs";                                   // Key of module attributes parameter.
+const CFG_KEY : &str = "cfg";                                       // Key of module cfg predicate parameter.
+const FIXTURES_KEY : &str = "fixtures";                             // Key of fixtures folder parameter.
+const CONFINE_KEY : &str = "confine";                               // Key of path confinement policy parameter.
+const ROOT_KEY : &str = "root";                                     // Key of named tests root parameter.
+const PARAMETERS_KEYS : [&str; 9] = [PATH_KEY, NAME_KEY, DIR_KEY, MODE_KEY, ATTRS_KEY, CFG_KEY, FIXTURES_KEY, CONFINE_KEY, ROOT_KEY];     // Keys accepted as parameters.
+const TESTS_BIN_CFG : &str = "test";                                // Default cfg predicate of unit tests modules
+const TESTS_BIN_CFG_KEY : &str = "tests_bin-cfg";                   // Key used to fetch custom default cfg predicate
+const TESTS_BIN_BENCH_FOLDER : &str = "tests/bench";                // Default bench tests bin base folder
+const TESTS_BIN_BENCH_FOLDER_KEY : &str = "tests_bin-bench-folder"; // Key used to fetch custom bench base folder
+const TESTS_BIN_DOC_FOLDER : &str = "tests/doc";                    // Default doc tests bin base folder
+const TESTS_BIN_DOC_FOLDER_KEY : &str = "tests_bin-doc-folder";     // Key used to fetch custom doc base folder
+const MARKDOWN_EXTENSION : &str = ".md";                            // Extension of markdown doc tests files.
+const TESTS_BIN_BENCH_CFG : &str = "bench";                         // Default cfg predicate of bench tests modules
+const TESTS_BIN_BENCH_CFG_KEY : &str = "tests_bin-bench-cfg";       // Key used to fetch custom default bench cfg predicate
+const MODE_MODULE : &str = "module";                                // Mode linking file with `#[path]` module.
+const MODE_INCLUDE : &str = "include";                              // Mode including file in module with prelude.
+const TESTS_BIN_PRELUDE_KEY : &str = "tests_bin-prelude";           // Key used to fetch prelude of include mode
+const CONFINE_STRICT : &str = "strict";                             // Policy keeping paths and symbolic links targets in base folder.
+const CONFINE_LEXICAL : &str = "lexical";                           // Policy keeping paths in base folder, following symbolic links.
+const CONFINE_NONE : &str = "none";                                 // Policy allowing paths outside base folder.
+const TESTS_BIN_CONFINE_KEY : &str = "tests_bin-confine";           // Key used to fetch custom default confinement policy
+const RUST_EXTENSION : &str = ".rs";                                // Extension of unit tests files.
+const UNIT_MODULE_PREFIX : &str = "tests";                          // Prefix of unit tests module names generated from items
+const BENCH_MODULE_PREFIX : &str = "bench";                         // Prefix of bench tests module names generated from items
+const TESTS_BIN_PREFIX_KEY : &str = "tests_bin-prefix";             // Key used to fetch custom prefix of unit tests module names
+const TESTS_BIN_BENCH_PREFIX_KEY : &str = "tests_bin-bench-prefix";  // Key used to fetch custom prefix of bench tests module names
+const TESTS_BIN_FIXTURES_KEY : &str = "tests_bin-fixtures";         // Key used to fetch custom fixtures root folder
+const TESTS_BIN_ROOT_KEY : &str = "tests_bin-root-";                // Prefix of keys used to fetch named roots settings, followed by root name
+const ROOT_FOLDER_SUFFIX : &str = "-folder";                        // Suffix of key declaring named root folder
+const ROOT_CFG_SUFFIX : &str = "-cfg";                              // Suffix of key of named root default cfg predicate
+const ROOT_PREFIX_SUFFIX : &str = "-prefix";                        // Suffix of key of named root prefix of module names
+const OUT_DIR : &str = "OUT_DIR";                                   // Build script output directory key
+const AUTO_SETTINGS_FILE : &str = "tests_bin_auto.toml";            // Builder settings written in OUT_DIR by tests_bin_build
+const AUTO_FOLDER_KEY : &str = "folder";                            // Key of folder in builder settings
+const AUTO_CFG_KEY : &str = "cfg";                                  // Key of cfg predicate in builder settings
+const AUTO_NAME_KEY : &str = "name";                                // Key of root module name in builder settings
+const MIRROR_SOURCE_FOLDER : &str = "src";                          // Source folder mirrored by tests bin folder in mirror mode
+const MIRROR_FOLDER_MODULE : &str = "mod.rs";                       // File name of a folder module, given to mirror files sharing a folder name
+const REPORT_WARN : &str = "warn";                                  // Mirror mismatches are reported as warnings.
+const REPORT_ERROR : &str = "error";                                // Mirror mismatches are reported as errors.
+const REPORT_NONE : &str = "none";                                  // Mirror mismatches aren't reported.
+const TESTS_BIN_MIRROR_REPORT_KEY : &str = "tests_bin-mirror-report"; // Key used to fetch report level of mirror mismatches
+const UNIT_METHOD_ATTRIBUTE : &str = "unit_test_file";              // Helper attribute linking unit tests of an impl block method
+const BENCH_METHOD_ATTRIBUTE : &str = "bench_test_file";            // Helper attribute linking bench tests of an impl block method
+
+/// Kind of tests linked by macros, each with its own base folder and default cfg predicate.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum TestsKind {
+    /// Unit tests linked by `unit__tests!` and `#[unit_tests]`.
+    Unit,
+
+    /// Benchmarks linked by `bench__tests!` and `#[bench_tests]`.
+    Bench,
+}
+
+impl TestsKind {
+
+    /// Get the base folder of tests kind.
+    pub fn base_folder(&self) -> String {
+        match self {
+            TestsKind::Unit => get_tests_bin_base_folder(),
+            TestsKind::Bench => get_tests_bin_bench_folder(),
+        }
+    }
+
+    /// Get the default cfg predicate of tests kind.
+    pub fn default_cfg(&self) -> String {
+        match self {
+            TestsKind::Unit => get_tests_bin_cfg(),
+            TestsKind::Bench => get_tests_bin_bench_cfg(),
+        }
+    }
+
+    /// Get the prefix of module names generated from items.
+    pub fn module_prefix(&self) -> String {
+        match self {
+            TestsKind::Unit => get_setting(TESTS_BIN_PREFIX_KEY).unwrap_or(String::from(UNIT_MODULE_PREFIX)),
+            TestsKind::Bench => get_setting(TESTS_BIN_BENCH_PREFIX_KEY).unwrap_or(String::from(BENCH_MODULE_PREFIX)),
+        }
+    }
+
+    /// Get the helper attribute of `impl` block methods.
+    pub fn method_attribute(&self) -> &'static str {
+        match self {
+            TestsKind::Unit => UNIT_METHOD_ATTRIBUTE,
+            TestsKind::Bench => BENCH_METHOD_ATTRIBUTE,
+        }
+    }
+
+}
+
+/// Root folder of tests with its default cfg predicate and prefix of module names.
+pub(crate) struct TestsRoot {
+    pub folder : String,
+    pub cfg : String,
+    pub prefix : String,
+}
+
+impl TestsRoot {
+
+    /// Get the default root of tests kind.
+    pub fn of_kind(kind: TestsKind) -> TestsRoot {
+        TestsRoot { folder: kind.base_folder(), cfg: kind.default_cfg(), prefix: kind.module_prefix() }
+    }
+
+    /// Get a root declared in settings with `root-{name}-folder`, like `root-conformance-folder = "../conformance"`.
+    /// 
+    /// Default cfg predicate is given with `root-{name}-cfg`, default cfg predicate of tests kind if not set.
+    /// Prefix of module names is given with `root-{name}-prefix`, root name if not set.
+    /// 
+    /// Returns `None` if root isn't declared.
+    pub fn named(kind: TestsKind, name: &str) -> Option<TestsRoot> {
+        let key = format!("{}{}", TESTS_BIN_ROOT_KEY, name);
+        Some(TestsRoot {
+            folder: get_setting(&format!("{}{}", key, ROOT_FOLDER_SUFFIX))?,
+            cfg: get_setting(&format!("{}{}", key, ROOT_CFG_SUFFIX)).unwrap_or_else(|| kind.default_cfg()),
+            prefix: get_setting(&format!("{}{}", key, ROOT_PREFIX_SUFFIX)).unwrap_or(name.to_string()),
+        })
+    }
+
+    /// Get the folder fixtures are relative to, the root folder by default.
+    pub fn fixtures_folder(&self) -> String {
+        get_setting(TESTS_BIN_FIXTURES_KEY).unwrap_or_else(|| self.folder.clone())
+    }
+
+}
+
+/// Unit tests module to link.
+pub(crate) struct UnitTestModule {
+    pub module_name : String,
+    pub source : UnitTestModuleSource,
+}
+
+/// Source of a unit tests module.
+pub(crate) enum UnitTestModuleSource {
+    /// Module linked to a unit tests file full path.
+    File(PathBuf),
+
+    /// Module of a folder full path, containing its sub modules.
+    Folder(PathBuf, Vec<UnitTestModule>),
+}
+
+/// Linking mode of unit tests files.
+#[derive(PartialEq)]
+pub(crate) enum UnitTestMode {
+    /// File is linked as module with `#[path]`.
+    Module,
+
+    /// File is included in module with `use super::*;` and prelude.
+    Include,
+}
+
+/// Policy confining linked paths to the base folder.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum ConfinePolicy {
+    /// Paths and symbolic links targets must stay in base folder.
+    Strict,
+
+    /// Paths must stay in base folder, symbolic links are followed anywhere.
+    Lexical,
+
+    /// Paths aren't checked.
+    None,
+}
+
+impl ConfinePolicy {
+
+    /// Parse policy from its name, reporting incorrect names on span.
+    fn parse(policy: &str, span: Span, errors: &mut Vec<TestsBinError>) -> ConfinePolicy {
+        match policy {
+            CONFINE_STRICT => ConfinePolicy::Strict,
+            CONFINE_LEXICAL => ConfinePolicy::Lexical,
+            CONFINE_NONE => ConfinePolicy::None,
+            _ => {
+                errors.push(TestsBinError::new(span, TestsBinErrors::IncorrectConfine(policy.to_string())));
+                ConfinePolicy::Strict
+            },
+        }
+    }
+
+    /// Check that path relative to folder is confined by policy, reporting escapes on span.
+    fn check(&self, folder: &Path, base_folder: &str, path: &str, span: Span, errors: &mut Vec<TestsBinError>) {
+
+        if *self != ConfinePolicy::None && !is_lexically_inside(folder, path) {
+            errors.push(TestsBinError::new(span, TestsBinErrors::PathOutsideFolder { path: path.to_string(), folder: base_folder.to_string() }));
+        } else if *self == ConfinePolicy::Strict && !is_canonically_inside(folder, path) {
+            errors.push(TestsBinError::new(span, TestsBinErrors::SymlinkOutsideFolder { path: path.to_string(), folder: base_folder.to_string() }));
+        }
+
+    }
+
+}
+
+/// Report level of mirror mode mismatches.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum MirrorReport {
+    /// Mismatches are warnings.
+    Warn,
+
+    /// Mismatches are errors.
+    Error,
+
+    /// Mismatches aren't reported.
+    None,
+}
+
+impl MirrorReport {
+
+    /// Parse report level from its name, reporting incorrect names on span.
+    fn parse(report: &str, span: Span, errors: &mut Vec<TestsBinError>) -> MirrorReport {
+        match report {
+            REPORT_WARN => MirrorReport::Warn,
+            REPORT_ERROR => MirrorReport::Error,
+            REPORT_NONE => MirrorReport::None,
+            _ => {
+                errors.push(TestsBinError::new(span, TestsBinErrors::IncorrectMirrorReport(report.to_string())));
+                MirrorReport::Warn
+            },
+        }
+    }
+
+}
 
 /// Parameters of unit test macros.
 pub(crate) struct UnitTestParameters {
-    pub full_path : String,
-    pub module_name : String,
+    pub modules : Vec<UnitTestModule>,
+    pub mode : UnitTestMode,
+    pub prelude : TokenStream,
+    pub attributes : TokenStream,
+    pub cfg : TokenStream,
+    pub fixtures : Option<String>,      // Fixtures folder path relative to manifest directory
+    pub root : Option<String>,          // Project root when built outside of cargo, used by fixtures
+    pub warnings : Vec<TestsBinWarning>,
 }
 
 /// Extract unit tests parameters path and module name from attributes.
 /// 
-/// If no module name specified, it will be generated from item.
+/// Paths are relative to the base folder of tests kind, or to the folder of a named root given with `root = "name"`.
 /// 
-/// Panic(s)
-/// Will panic! if parameters are incorrect.
-#[inline(always)]
-pub(crate) fn extract_unit_tests_parameters(attr: TokenStream, item: TokenStream) -> UnitTestParameters {
+/// Parameters are positional `"path", "module_name"` and/or named `key = "value"` with keys `path`, `name`, `dir` 
+/// and `mode`. Attributes of generated module are given with `attrs(attribute, ...)`.
+/// 
+/// Values are string literals, raw or not, or string macros `concat!(...)` and `env!("VAR")`.
+/// 
+/// Fixtures folder given with `fixtures = "folder"` is relative to fixtures root folder and exposed to modules.
+/// 
+/// Modules are gated by cfg predicate given with `cfg = "predicate"` or `cfg(predicate)`, [`get_tests_bin_cfg`] by default.
+/// 
+/// If no module name specified, it will be generated from item or from path if no item. Items with an owner,
+/// like methods of an `impl` block, are named after owner and item.
+/// 
+/// If path is a glob pattern, a module is linked for each matching file with a name generated from its path.
+/// 
+/// If no parameters are given to `#[unit_tests]`, path is derived from item with convention of [`get_tests_bin_convention`].
+/// 
+/// If path is given with `dir = "folder"`, a module tree mirroring the folder is linked. The root module is named
+/// after the folder unless a module name is specified.
+/// 
+/// With `mode = "include"`, files are included in modules with `use super::*;` and prelude of [`get_tests_bin_prelude`].
+/// 
+/// Linked paths must stay in base folder according to policy given with `confine = "policy"`, [`get_tests_bin_confine`] by default.
+/// 
+/// Error(s)
+/// Returns every errors found in parameters, each pointing at the faulty token.
+pub(crate) fn extract_unit_tests_parameters(kind: TestsKind, attr: TokenStream, item: Option<TokenStream>, owner: Option<&str>) -> Result<UnitTestParameters, Vec<TestsBinError>> {
 
-    let mut full_path : Option<String> = None;
-    let mut module_name : Option<String> = None;
-    let mut separator : bool = false;
+    let mut path : Option<(String, Span)> = None;
+    let mut module_name : Option<(String, Span)> = None;
+    let mut mode : Option<(String, Span)> = None;
+    let mut fixtures : Option<(String, Span)> = None;
+    let mut confine : Option<(String, Span)> = None;
+    let mut root : Option<(String, Span)> = None;
+    let mut attributes : Option<TokenStream> = None;
+    let mut cfg : Option<(String, Span)> = None;                 // Cfg predicate given as literal
+    let mut cfg_tokens : Option<TokenStream> = None;            // Cfg predicate given with `cfg(...)`
+    let mut is_dir : bool = false;                  // Path given with `dir` key
+    let mut key : Option<(String, Span)> = None;    // Key found, waiting for `=` and value
+    let mut assign : bool = false;                  // `=` found after key
+    let mut separator : bool = true;                // No separator needed before first parameter
+    let mut errors : Vec<TestsBinError> = Vec::new();
 
-    // Extract parameters
-    attr.into_iter().for_each(|token| {
-        // Syntax error because of extra tokens
-        if full_path != Option::None && module_name != Option::None {
-            panic!("{}", TestsBinErrors::IncorrectParameters.to_string());
-        }
+    // Expand string macros to literals. Values that can't be evaluated stop extraction after syntax check.
+    let attr = expand_string_macros(attr, &mut errors);
+    let mut invalid_value : bool = !errors.is_empty();
+
+    // Settings files using unsupported syntax would silently fall back to defaults
+    if let Some(error) = get_settings_error() {
+        errors.push(TestsBinError::new(Span::call_site(), error));
+    }
 
+    // Extract parameters
+    for token in attr {
         match token {
-            
-            proc_macro::TokenTree::Punct(punct) => {
+            TokenTree::Punct(punct) => {
                     match punct.as_char() {
-                        PARAMETERS_SEPARATOR => separator = true,  // Only PARAMETERS_SEPARATOR allowed
-                        _ => panic!("{}", TestsBinErrors::IncorrectParameters.to_string()), // Anything else is a syntax error.
+                        PARAMETERS_SEPARATOR if key.is_none() => separator = true,  // Only PARAMETERS_SEPARATOR allowed
+                        PARAMETERS_ASSIGN if key.is_some() && !assign => assign = true,  // Or `=` after a key
+                        _ => errors.push(TestsBinError::new(punct.span(), TestsBinErrors::IncorrectParameters)), // Anything else is a syntax error.
                     }
                 },
-            proc_macro::TokenTree::Literal(lit) => {
-                let parameter = lit.to_string().replace("\"", "");  // Extract parameter and remove ""
-
-                if full_path == Option::None {  // If full_path has no value, it is the full path.
-                    match std::env::var(CARGO_MANIFEST_DIR){
-                        Ok(value) => {
-                            // Windows only instruction
-                            #[cfg(windows)]
-                            {
-                                full_path = Some(format!("{}/{}/{}", value, get_tests_bin_base_folder(), parameter).replace("\\", "\\\\"));
-                            }
-
-                            // All other Os
-                            #[cfg(not(windows))]
-                            {
-                                full_path = Some(format!("{}/{}/{}", value, get_tests_bin_base_folder(), parameter));
-                            }
-                        },
-                        Err(_) => panic!("Env variable `{}` not set!", CARGO_MANIFEST_DIR),
-                    }
-                } else {    // Else it is the module name parameter.
-                    if separator {
-                        module_name = Some(parameter);
-                    } else {
-                        panic!("{}", TestsBinErrors::IncorrectParameters.to_string()); // Misssing `,`separator.
-                    }
+
+            // Keys start a new parameter
+            TokenTree::Ident(ident) if key.is_none() => {
+                if !separator {
+                    errors.push(TestsBinError::new(ident.span(), TestsBinErrors::MissingSeparator)); // Misssing `,`separator.
                 }
+
+                if !PARAMETERS_KEYS.contains(&ident.to_string().as_str()) {
+                    errors.push(TestsBinError::new(ident.span(), TestsBinErrors::UnknownParameter(ident.to_string(), PARAMETERS_KEYS.to_vec())));
+                }
+                key = Some((ident.to_string(), ident.span()));
+            },
+
+            // Attributes of generated module
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis && matches!(&key, Some((key_name, _)) if key_name == ATTRS_KEY) => {
+                if attributes.is_some() {
+                    errors.push(TestsBinError::new(group.span(), TestsBinErrors::DuplicateParameter(String::from(ATTRS_KEY))));
+                }
+                if assign {
+                    errors.push(TestsBinError::new(group.span(), TestsBinErrors::IncorrectParameters)); // No `=` before `attrs(...)`.
+                }
+                attributes = Some(extract_attributes(group.stream(), &mut errors));
+                key = None;
+                assign = false;
+                separator = false;
+            },
+
+            // Cfg predicate of generated module
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis && matches!(&key, Some((key_name, _)) if key_name == CFG_KEY) => {
+                if cfg.is_some() || cfg_tokens.is_some() {
+                    errors.push(TestsBinError::new(group.span(), TestsBinErrors::DuplicateParameter(String::from(CFG_KEY))));
+                }
+                if assign {
+                    errors.push(TestsBinError::new(group.span(), TestsBinErrors::IncorrectParameters)); // No `=` before `cfg(...)`.
+                }
+                cfg_tokens = Some(group.stream());
+                key = None;
+                assign = false;
+                separator = false;
+            },
+
+            TokenTree::Literal(lit) => {
+                // Extract parameter without "" and escapes
+                let parameter = match decode_string_literal(lit.to_string().as_str()) {
+                    Some(parameter) => parameter,
+                    None => {
+                        errors.push(TestsBinError::new(lit.span(), TestsBinErrors::IncorrectLiteral(lit.to_string())));
+                        invalid_value = true;
+                        String::new()
+                    },
+                };
+
+                match key.take() {
+                    // Value of a key
+                    Some((key_name, key_span)) => {
+                        if !assign {
+                            errors.push(TestsBinError::new(lit.span(), TestsBinErrors::IncorrectParameters)); // Missing `=` after key.
+                        }
+
+                        let value = match key_name.as_str() {
+                            PATH_KEY => &mut path,
+                            DIR_KEY => { is_dir = true; &mut path },
+                            NAME_KEY => &mut module_name,
+                            MODE_KEY => &mut mode,
+                            FIXTURES_KEY => &mut fixtures,
+                            CONFINE_KEY => &mut confine,
+                            ROOT_KEY => &mut root,
+                            CFG_KEY => {
+                                if cfg_tokens.is_some() {
+                                    errors.push(TestsBinError::new(key_span, TestsBinErrors::DuplicateParameter(key_name.clone())));
+                                }
+                                &mut cfg
+                            },
+                            ATTRS_KEY => {
+                                errors.push(TestsBinError::new(lit.span(), TestsBinErrors::IncorrectAttributes)); // Attributes aren't a literal.
+                                &mut None
+                            },
+                            _ => &mut None,    // Unknown key already reported.
+                        };
+
+                        if value.is_some() {
+                            errors.push(TestsBinError::new(key_span, TestsBinErrors::DuplicateParameter(key_name)));
+                        }
+                        *value = Some((parameter, lit.span()));
+                    },
+
+                    // Positional path and module name
+                    None => {
+                        if !separator {
+                            errors.push(TestsBinError::new(lit.span(), TestsBinErrors::MissingSeparator)); // Misssing `,`separator.
+                        }
+
+                        if path.is_none() {  // If path has no value, it is the path.
+                            path = Some((parameter, lit.span()));
+                        } else if module_name.is_none() {    // Else it is the module name parameter.
+                            module_name = Some((parameter, lit.span()));
+                        } else {    // Syntax error because of extra parameter.
+                            errors.push(TestsBinError::new(lit.span(), TestsBinErrors::TooManyParameters));
+                        }
+                    },
+                }
+
+                assign = false;
+                separator = false;
             },
 
             // Anything else is a syntax error.
-            _ => panic!("{}", TestsBinErrors::IncorrectParameters.to_string()),
+            _ => errors.push(TestsBinError::new(token.span(), TestsBinErrors::IncorrectParameters)),
+        }
+    }
+
+    // Key without value, unless already reported by a syntax error
+    match key {
+        Some((_, key_span)) if errors.is_empty() => errors.push(TestsBinError::new(key_span, TestsBinErrors::IncorrectParameters)),
+        _ => {},
+    }
+
+    // Values that can't be evaluated would only give misleading path errors
+    if invalid_value {
+        return Err(errors);
+    }
+
+    // Get named root, root of tests kind by default. Paths of undeclared root can't be resolved.
+    let root = match root {
+        Some((name, span)) => match TestsRoot::named(kind, &name) {
+            Some(root) => root,
+            None => {
+                errors.push(TestsBinError::new(span, TestsBinErrors::RootNotDeclared(name)));
+                return Err(errors);
+            },
+        },
+        None => TestsRoot::of_kind(kind),
+    };
+
+    // Get mode, module by default
+    let mode = match mode {
+        Some((mode, span)) => match mode.as_str() {
+            MODE_MODULE => UnitTestMode::Module,
+            MODE_INCLUDE => UnitTestMode::Include,
+            _ => {
+                errors.push(TestsBinError::new(span, TestsBinErrors::IncorrectMode(mode)));
+                UnitTestMode::Module
+            },
+        },
+        None => UnitTestMode::Module,
+    };
+
+    // Cfg predicate must be valid, given or default
+    let cfg = match (cfg_tokens, cfg) {
+        (Some(predicate), _) => {
+            if let Err(span) = validate_cfg(predicate.clone()) {
+                errors.push(TestsBinError::new(span.unwrap_or(Span::call_site()), TestsBinErrors::IncorrectCfg(predicate.to_string())));
+            }
+            predicate
+        },
+        (None, Some((cfg, span))) => parse_cfg(&cfg, span, &mut errors),
+        (None, None) => parse_cfg(&root.cfg, Span::call_site(), &mut errors),
+    };
+
+    // Get confinement policy, default policy if not given
+    let confine = match confine {
+        Some((confine, span)) => ConfinePolicy::parse(&confine, span, &mut errors),
+        None => ConfinePolicy::parse(&get_tests_bin_confine(), Span::call_site(), &mut errors),
+    };
+
+    // Prelude must be valid tokens since it is injected in include mode
+    let prelude = get_tests_bin_prelude();
+    let prelude = match prelude.parse::<TokenStream>() {
+        Ok(prelude) => prelude,
+        Err(_) => {
+            if mode == UnitTestMode::Include {
+                errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::IncorrectPrelude(prelude)));
+            }
+            TokenStream::new()
+        },
+    };
+
+    // Item must be of a kind that can have a tests module
+    if let Some(item) = &item {
+        match parse_item_signature(item.clone()) {
+            Some(signature) if is_supported_kind(signature.kind) => {},
+            Some(signature) => errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::UnsupportedItem(Some(signature.kind.to_string())))),
+            None => errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::UnsupportedItem(None))),
+        }
+    }
+
+    // Path is mandatory unless it can be derived from item
+    let (path, path_span) = match path {
+        Some(path) => path,
+        None if errors.is_empty() && item.is_some() => match item.clone().and_then(derive_item_path) {
+            Some(path) => (path, Span::call_site()),
+            None => {
+                errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::PathNotDerivable));
+                return Err(errors);
+            },
+        },
+        None => {
+            if errors.is_empty() {
+                errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::MissingPath));
+            }
+            return Err(errors);
+        },
+    };
+
+    // Get tests bin folder from project root
+    let manifest_dir = match get_project_root() {
+        Some(value) => value,
+        None => {
+            errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::ProjectRootNotFound));
+            return Err(errors);
+        },
+    };
+    let base_folder = root.folder.clone();
+    let folder = Path::new(&manifest_dir).join(&base_folder);
+
+    // Fixtures folder must exist in fixtures root folder
+    let fixtures = match fixtures {
+        Some((fixtures, span)) => {
+            let fixtures_folder = root.fixtures_folder();
+            let root = Path::new(&manifest_dir).join(&fixtures_folder);
+            if !root.join(&fixtures).is_dir() {
+                errors.push(TestsBinError::new(span, TestsBinErrors::FixturesNotFound { path: fixtures.clone(), folder: fixtures_folder.clone() }));
+            }
+            confine.check(&root, &fixtures_folder, &fixtures, span, &mut errors);
+            Some(format!("{}/{}/", fixtures_folder.trim_end_matches('/'), fixtures.trim_end_matches('/')))
+        },
+        None => None,
+    };
+
+    let mut modules : Vec<UnitTestModule> = Vec::new();
+
+    if is_dir {
+        // Root module is named after the folder unless specified.
+        let module_name = match module_name {
+            Some((name, span)) => {
+                if !is_valid_identifier(&name) {
+                    errors.push(TestsBinError::new(span, TestsBinErrors::IncorrectModuleName(name.clone())));
+                }
+                name
+            },
+            None => register_module_name(generate_ident(path.trim_end_matches('/').rsplit('/').next().unwrap_or_default()), &path),
+        };
+
+        // Link every unit tests file of folder and sub folders
+        let dir_folder = folder.join(&path);
+        let files : Vec<String> = list_files(&dir_folder).into_iter().filter(|file| file.ends_with(RUST_EXTENSION)).collect();
+        track_folder(&dir_folder);
+
+        // Folder and each of its files must be confined
+        confine.check(&folder, &base_folder, &path, path_span, &mut errors);
+        for file in files.iter() {
+            confine.check(&folder, &base_folder, format!("{}/{}", path.trim_end_matches('/'), file).as_str(), path_span, &mut errors);
+        }
+
+        if !dir_folder.is_dir() {
+            errors.push(TestsBinError::new(path_span, TestsBinErrors::FolderNotFound { path: path.clone(), folder: base_folder.clone() }));
+        } else if files.is_empty() {
+            errors.push(TestsBinError::new(path_span, TestsBinErrors::EmptyFolder { path: path.clone(), folder: base_folder.clone() }));
+        } else {
+            let dir = format!("{}/", path.trim_end_matches('/'));
+            let children = generate_module_tree(&folder, &base_folder, &dir, &files, path_span, &mut errors);
+            modules.push(UnitTestModule { module_name, source: UnitTestModuleSource::Folder(dir_folder, children) });
+        }
+    } else if is_glob_pattern(&path) {
+        // Module name can't be shared by multiple modules
+        if let Some((_, span)) = module_name {
+            errors.push(TestsBinError::new(span, TestsBinErrors::GlobModuleName));
+        }
+
+        // Link every file matching pattern
+        let files = glob_files(&folder, &path);
+        track_folder(&folder);
+        if files.is_empty() {
+            errors.push(TestsBinError::new(path_span, TestsBinErrors::NoGlobMatch { pattern: path.clone(), folder: base_folder.clone() }));
+        }
+
+        for file in files {
+            confine.check(&folder, &base_folder, &file, path_span, &mut errors);
+            modules.push(UnitTestModule { source: UnitTestModuleSource::File(folder.join(&file)), module_name: register_module_name(generate_ident(&file), &file) });
+        }
+    } else {
+        // Validate module name given or generate it from item or path.
+        let module_name = match module_name {
+            Some((name, span)) => {
+                if !is_valid_identifier(&name) {
+                    errors.push(TestsBinError::new(span, TestsBinErrors::IncorrectModuleName(name.clone())));
+                }
+                name
+            },
+            None => match item {
+                Some(item) => register_module_name(generate_test_mod_name(&root.prefix, owner, item), &path),
+                None => register_module_name(generate_ident(&path), &path),
+            },
+        };
+
+        // Make sure unit tests file exists and is confined
+        if !folder.join(&path).is_file() {
+            errors.push(TestsBinError::new(path_span, TestsBinErrors::FileNotFound { path: path.clone(), folder: base_folder.clone(), suggestions: suggest_files(&folder, &path) }));
         }
-    });
+        confine.check(&folder, &base_folder, &path, path_span, &mut errors);
 
-    // Generate module name if none for unit_tests attribute macros
-    if module_name == Option::None {
-        module_name = Some(generate_test_mod_name(item));
+        modules.push(UnitTestModule { source: UnitTestModuleSource::File(folder.join(&path)), module_name });
     }
 
-    // Return parameters
-    UnitTestParameters{ full_path: full_path.unwrap(), module_name: module_name.unwrap() }
+    // Return parameters or all errors found
+    if errors.is_empty() {
+        Ok(UnitTestParameters{ modules, mode, prelude, attributes: attributes.unwrap_or_default(), cfg, fixtures, root: get_project_root_outside_cargo(), warnings: Vec::new() })
+    } else {
+        Err(errors)
+    }
 
 }
 
-/// Get the tests_bin base folder as string
-#[inline(always)]
-pub(crate) fn get_tests_bin_base_folder() -> String {
+/// Parameters of doc tests macro.
+pub(crate) struct DocTestParameters {
+    pub link_path : String,     // Path relative to invoking source file
+    pub code : bool,        // `.rs` file to wrap in a code fence
+}
+
+/// Extract doc tests path parameter from attributes.
+/// 
+/// Path must be a `.md` file or a `.rs` file, relative to [`get_tests_bin_doc_folder`].
+/// 
+/// Error(s)
+/// Returns every errors found in parameters, each pointing at the faulty token.
+pub(crate) fn extract_doc_tests_parameters(attr: TokenStream) -> Result<DocTestParameters, Vec<TestsBinError>> {
+
+    let mut path : Option<(String, Span)> = None;
+    let mut errors : Vec<TestsBinError> = Vec::new();
 
-    match std::env::var(TESTS_BIN_BASE_FOLDER_KEY) {
-        Ok(base_folder) => base_folder,         // Return base folder for config.toml
-        Err(_) => String::from(TESTS_BIN_BASE_FOLDER)   // Key not found, return base folder.
+    // Settings files using unsupported syntax would silently fall back to defaults
+    if let Some(error) = get_settings_error() {
+        errors.push(TestsBinError::new(Span::call_site(), error));
     }
+
+    // Extract path, the only parameter
+    for token in expand_string_macros(attr, &mut errors) {
+        match token {
+            TokenTree::Literal(lit) if path.is_none() => match decode_string_literal(lit.to_string().as_str()) {
+                Some(parameter) => path = Some((parameter, lit.span())),
+                None => errors.push(TestsBinError::new(lit.span(), TestsBinErrors::IncorrectLiteral(lit.to_string()))),
+            },
+            _ => errors.push(TestsBinError::new(token.span(), TestsBinErrors::IncorrectDocParameters)),
+        }
+    }
+
+    let (path, path_span) = match path {
+        Some(path) => path,
+        None => {
+            if errors.is_empty() {
+                errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::IncorrectDocParameters));
+            }
+            return Err(errors);
+        },
+    };
+
+    // Only markdown and rust files are supported.
+    let code = path.ends_with(RUST_EXTENSION);
+    if !code && !path.ends_with(MARKDOWN_EXTENSION) {
+        errors.push(TestsBinError::new(path_span, TestsBinErrors::IncorrectDocParameters));
+    }
+
+    // Get doc tests folder from project root
+    let manifest_dir = match get_project_root() {
+        Some(value) => value,
+        None => {
+            errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::ProjectRootNotFound));
+            return Err(errors);
+        },
+    };
+    let base_folder = get_tests_bin_doc_folder();
+    let folder = Path::new(&manifest_dir).join(&base_folder);
+
+    // Make sure doc tests file exists and is confined by default policy
+    if !folder.join(&path).is_file() {
+        errors.push(TestsBinError::new(path_span, TestsBinErrors::FileNotFound { path: path.clone(), folder: base_folder.clone(), suggestions: suggest_files(&folder, &path) }));
+    }
+    ConfinePolicy::parse(&get_tests_bin_confine(), Span::call_site(), &mut errors).check(&folder, &base_folder, &path, path_span, &mut errors);
+
+    if errors.is_empty() {
+        Ok(DocTestParameters { link_path: get_include_path(&folder.join(&path)), code })
+    } else {
+        Err(errors)
+    }
+
 }
 
-/// Generate tests module name from attributes and item tokens
+/// Extract parameters of the module tree linking every unit tests file of the tests bin folder, with the settings
+/// written in `OUT_DIR` by `tests_bin_build`. No parameters are accepted.
 /// 
-/// Will replace illegal characters of filename with _
+/// Folder, cfg predicate and root module name given to the builder replace [`get_tests_bin_base_folder`], [`get_tests_bin_cfg`]
+/// and the folder name. A missing or empty folder links nothing, since the build script already reports it.
 /// 
-/// Cannot be unit tested because of TokenStream.
-#[inline(always)]
-pub(crate) fn generate_test_mod_name(item: TokenStream) -> String {
+/// Error(s)
+/// Returns errors if parameters are given, `OUT_DIR` isn't set or the builder settings weren't written.
+pub(crate) fn extract_auto_tests_parameters(attr: TokenStream) -> Result<UnitTestParameters, Vec<TestsBinError>> {
+
+    let mut errors : Vec<TestsBinError> = Vec::new();
 
-    // Module name string that accumulate characters
-    let mut module_name = String::new();
+    // Settings files using unsupported syntax would silently fall back to defaults
+    if let Some(error) = get_settings_error() {
+        errors.push(TestsBinError::new(Span::call_site(), error));
+    }
+
+    if let Some(token) = attr.into_iter().next() {
+        errors.push(TestsBinError::new(token.span(), TestsBinErrors::UnexpectedParameters));
+    }
 
-    // Add tokenstream idents to name
-    for token in item {
+    // Builder settings are tracked so that running the build script again expands macro again
+    let auto_settings = std::env::var(OUT_DIR).ok().map(|out_dir| Path::new(&out_dir).join(AUTO_SETTINGS_FILE));
+    let builder = match auto_settings.as_ref().and_then(|file| std::fs::read_to_string(file).ok().zip(Some(file))) {
+        Some((content, file)) => {
+            track_file(file);
+            parse_table(&content, "").unwrap_or_default()
+        },
+        None => {
+            errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::ModuleTreeNotGenerated));
+            return Err(errors);
+        },
+    };
+
+    let root = TestsRoot::of_kind(TestsKind::Unit);
+    let base_folder = builder.get(AUTO_FOLDER_KEY).cloned().unwrap_or(root.folder);
+    let cfg = parse_cfg(builder.get(AUTO_CFG_KEY).unwrap_or(&root.cfg), Span::call_site(), &mut errors);
+    let base_folder_path = base_folder.trim_end_matches('/');
+    let module_name = builder.get(AUTO_NAME_KEY).cloned().unwrap_or_else(|| generate_ident(base_folder_path.rsplit('/').next().unwrap_or_default()));
+    if !is_valid_identifier(&module_name) {
+        errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::IncorrectModuleName(module_name.clone())));
+    }
+
+    // Get tests bin folder from project root
+    let manifest_dir = match get_project_root() {
+        Some(value) => value,
+        None => {
+            errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::ProjectRootNotFound));
+            return Err(errors);
+        },
+    };
+    let folder = Path::new(&manifest_dir).join(&base_folder);
+    track_folder(&folder);
+
+    // Link every unit tests file of folder and sub folders
+    let files : Vec<String> = list_files(&folder).into_iter().filter(|file| file.ends_with(RUST_EXTENSION)).collect();
+    let mut modules : Vec<UnitTestModule> = Vec::new();
+    if !files.is_empty() {
+        let children = generate_module_tree(&folder, base_folder_path, "", &files, Span::call_site(), &mut errors);
+        modules.push(UnitTestModule { module_name: register_module_name(module_name, &base_folder), source: UnitTestModuleSource::Folder(folder, children) });
+    }
+
+    match errors.is_empty() {
+        true => Ok(UnitTestParameters{ modules, mode: UnitTestMode::Module, prelude: TokenStream::new(), attributes: TokenStream::new(), cfg, fixtures: None, root: get_project_root_outside_cargo(), warnings: Vec::new() }),
+        false => Err(errors),
+    }
+
+}
+
+/// Extract parameters of mirror mode, linking the mirror tests file of each source file. No parameters are accepted.
+/// 
+/// Each `.rs` file of `src` is mirrored by the file of the same relative path in the tests bin folder, so that
+/// `src/physics/aabb.rs` is tested by `tests/unit/physics/aabb.rs`. Mirror files are linked in a module tree named
+/// after the tests bin folder, like `unit::physics::aabb`, and gated by [`get_tests_bin_cfg`].
+/// 
+/// Source files without mirror and mirror files without source are reported according to [`get_tests_bin_mirror_report`].
+/// 
+/// Error(s)
+/// Returns errors if parameters are given, settings are incorrect or mismatches are reported as errors.
+pub(crate) fn extract_mirror_tests_parameters(attr: TokenStream) -> Result<UnitTestParameters, Vec<TestsBinError>> {
+
+    let mut errors : Vec<TestsBinError> = Vec::new();
+
+    // Settings files using unsupported syntax would silently fall back to defaults
+    if let Some(error) = get_settings_error() {
+        errors.push(TestsBinError::new(Span::call_site(), error));
+    }
+
+    if let Some(token) = attr.into_iter().next() {
+        errors.push(TestsBinError::new(token.span(), TestsBinErrors::UnexpectedParameters));
+    }
+
+    let root = TestsRoot::of_kind(TestsKind::Unit);
+    let cfg = parse_cfg(&root.cfg, Span::call_site(), &mut errors);
+    let report = MirrorReport::parse(&get_tests_bin_mirror_report(), Span::call_site(), &mut errors);
+
+    // Get source and tests bin folders from project root
+    let manifest_dir = match get_project_root() {
+        Some(value) => value,
+        None => {
+            errors.push(TestsBinError::new(Span::call_site(), TestsBinErrors::ProjectRootNotFound));
+            return Err(errors);
+        },
+    };
+    let base_folder = root.folder.clone();
+    let folder = Path::new(&manifest_dir).join(&base_folder);
+    let source_folder = Path::new(&manifest_dir).join(MIRROR_SOURCE_FOLDER);
+    track_folder(&source_folder);
+    track_folder(&folder);
+
+    let sources : Vec<String> = list_files(&source_folder).into_iter().filter(|file| file.ends_with(RUST_EXTENSION)).collect();
+    let mirrors : Vec<String> = list_files(&folder).into_iter().filter(|file| file.ends_with(RUST_EXTENSION)).collect();
+    let (matched, missing, orphans) = match_mirrors(&sources, &mirrors);
+
+    // Report mismatches
+    let base_folder_path = base_folder.trim_end_matches('/');
+    let mismatches = missing.iter()
+        .map(|file| TestsBinWarnings::MirrorNotFound { source: format!("{}/{}", MIRROR_SOURCE_FOLDER, file), mirror: format!("{}/{}", base_folder_path, file) })
+        .chain(orphans.iter().map(|file| TestsBinWarnings::SourceNotFound { mirror: format!("{}/{}", base_folder_path, file), source: format!("{}/{}", MIRROR_SOURCE_FOLDER, file) }));
+    let mut warnings : Vec<TestsBinWarning> = Vec::new();
+    match report {
+        MirrorReport::Warn => warnings.extend(mismatches.map(|mismatch| TestsBinWarning::new(Span::call_site(), mismatch))),
+        MirrorReport::Error => errors.extend(mismatches.map(|mismatch| TestsBinError::new(Span::call_site(), TestsBinErrors::MirrorMismatch(mismatch)))),
+        MirrorReport::None => {},
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Link mirror files in a module tree named after tests bin folder
+    let mut modules : Vec<UnitTestModule> = Vec::new();
+    if !matched.is_empty() {
+        let module_name = register_module_name(generate_ident(base_folder_path.rsplit('/').next().unwrap_or_default()), &base_folder);
+        modules.push(UnitTestModule { module_name, source: UnitTestModuleSource::Folder(folder.clone(), generate_mirror_tree(&folder, &matched)) });
+    }
+
+    Ok(UnitTestParameters{ modules, mode: UnitTestMode::Module, prelude: TokenStream::new(), attributes: TokenStream::new(), cfg, fixtures: None, root: get_project_root_outside_cargo(), warnings })
+
+}
+
+/// Parse and validate cfg predicate string, reporting errors on span since parsed tokens have no location.
+fn parse_cfg(cfg: &str, span: Span, errors: &mut Vec<TestsBinError>) -> TokenStream {
+
+    match cfg.parse::<TokenStream>() {
+        Ok(predicate) => {
+            if validate_cfg(predicate.clone()).is_err() {
+                errors.push(TestsBinError::new(span, TestsBinErrors::IncorrectCfg(cfg.to_string())));
+            }
+            predicate
+        },
+        Err(_) => {
+            errors.push(TestsBinError::new(span, TestsBinErrors::IncorrectCfg(cfg.to_string())));
+            TokenStream::new()
+        },
+    }
+
+}
+
+/// Extract attributes of `attrs(attribute, ...)` as `#[attribute]` tokens.
+/// 
+/// Each attribute must start with an identifier like `allow(dead_code)` or `doc = "..."`.
+fn extract_attributes(attrs: TokenStream, errors: &mut Vec<TestsBinError>) -> TokenStream {
+
+    let mut attributes = TokenStream::new();
+    let mut attribute : Vec<TokenTree> = Vec::new();
+
+    for token in attrs {
         match token {
-            proc_macro::TokenTree::Ident(ident) => module_name.push_str(format!("{}{}", ident.to_string().as_str(), ILLEGAL_CHARACTER_REPLACE).as_str()),
-            _ => {},    // Ignore anything else
+            TokenTree::Punct(punct) if punct.as_char() == PARAMETERS_SEPARATOR => push_attribute(&mut attributes, &mut attribute, errors),
+            _ => attribute.push(token),
         }
     }
+    push_attribute(&mut attributes, &mut attribute, errors);
 
-    // Remove last _ character
-    module_name.pop();
-        
-    // Return module name generated.
-    module_name
+    attributes
+
+}
+
+/// Push attribute tokens as `#[attribute]` to attributes and clear them.
+fn push_attribute(attributes: &mut TokenStream, attribute: &mut Vec<TokenTree>, errors: &mut Vec<TestsBinError>) {
+
+    match attribute.first() {
+        Some(TokenTree::Ident(_)) => {
+            attributes.extend([
+                TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+                TokenTree::Group(Group::new(Delimiter::Bracket, attribute.drain(..).collect())),
+            ]);
+        },
+        Some(token) => errors.push(TestsBinError::new(token.span(), TestsBinErrors::IncorrectAttributes)),
+        None => {},     // Empty attribute from trailing separator is tolerated.
+    }
+
+    attribute.clear();
+
+}
+
+/// Generate modules of a folder from its files relative paths, sub folders becoming nested modules.
+/// 
+/// dir is the folder relative path from base folder, ending with `/`.
+fn generate_module_tree(folder: &Path, base_folder: &str, dir: &str, files: &[String], span: Span, errors: &mut Vec<TestsBinError>) -> Vec<UnitTestModule> {
+
+    let mut modules : Vec<UnitTestModule> = Vec::new();
+    let mut sub_folders : BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    // Files are modules while sub folders files are grouped by sub folder
+    for file in files {
+        match file.split_once('/') {
+            Some((sub_folder, sub_file)) => sub_folders.entry(sub_folder.to_string()).or_default().push(sub_file.to_string()),
+            None => modules.push(UnitTestModule { 
+                module_name: generate_ident(file.trim_end_matches(RUST_EXTENSION)), 
+                source: UnitTestModuleSource::File(folder.join(dir).join(file)) }),
+        }
+    }
+
+    for (sub_folder, sub_files) in sub_folders {
+        let children = generate_module_tree(folder, base_folder, format!("{}{}/", dir, sub_folder).as_str(), &sub_files, span, errors);
+        modules.push(UnitTestModule { module_name: generate_ident(&sub_folder), source: UnitTestModuleSource::Folder(folder.join(dir).join(&sub_folder), children) });
+    }
+
+    // A file and a folder with the same name would define the same module twice.
+    let mut names : Vec<&str> = modules.iter().map(|module| module.module_name.as_str()).collect();
+    names.sort();
+    for pair in names.windows(2) {
+        if pair[0] == pair[1] {
+            errors.push(TestsBinError::new(span, TestsBinErrors::DuplicateModuleName { name: pair[0].to_string(), folder: format!("{}/{}", base_folder, dir) }));
+        }
+    }
+
+    modules
+
+}
+
+/// Generate modules of mirror files from their relative paths, sub folders becoming nested modules.
+/// 
+/// A file sharing its name with a sub folder, like `physics.rs` next to `physics/`, is linked as if it were `physics/mod.rs`
+/// so both layouts of source modules give the same tests modules.
+fn generate_mirror_tree(folder: &Path, files: &[String]) -> Vec<UnitTestModule> {
+
+    // Module path of each file with its path
+    let files : Vec<(String, String)> = files.iter().map(|file| {
+        let sub_folder = format!("{}/", file.trim_end_matches(RUST_EXTENSION));
+        match files.iter().any(|other| other.starts_with(&sub_folder)) {
+            true => (format!("{}{}", sub_folder, MIRROR_FOLDER_MODULE), file.clone()),
+            false => (file.clone(), file.clone()),
+        }
+    }).collect();
+
+    generate_mirror_modules(folder, folder, &files)
+
+}
+
+/// Generate modules of mirror files from their module paths relative to the current folder and their paths relative to folder.
+fn generate_mirror_modules(folder: &Path, current: &Path, files: &[(String, String)]) -> Vec<UnitTestModule> {
+
+    let mut modules : Vec<UnitTestModule> = Vec::new();
+    let mut sub_folders : BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+
+    for (module_path, file) in files {
+        match module_path.split_once('/') {
+            Some((sub_folder, sub_path)) => sub_folders.entry(sub_folder.to_string()).or_default().push((sub_path.to_string(), file.clone())),
+            None => modules.push(UnitTestModule { 
+                module_name: generate_ident(module_path.trim_end_matches(RUST_EXTENSION)), 
+                source: UnitTestModuleSource::File(folder.join(file)) }),
+        }
+    }
+
+    for (sub_folder, sub_files) in sub_folders {
+        let sub_folder_path = current.join(&sub_folder);
+        let children = generate_mirror_modules(folder, &sub_folder_path, &sub_files);
+        modules.push(UnitTestModule { module_name: generate_ident(&sub_folder), source: UnitTestModuleSource::Folder(sub_folder_path, children) });
+    }
+
+    modules
+
+}
+
+/// Get tests file path used by `include!` and `include_str!`, relative to the folder of the invoking source file
+/// so that builds don't depend on the checkout location.
+/// 
+/// Falls back to the full path if invoking source file isn't known, like in doc tests.
+pub(crate) fn get_include_path(path: &Path) -> String {
+
+    get_invoking_file()
+        .and_then(|file| file.parent().and_then(|folder| relative_path(folder, path)))
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+
+}
+
+/// Get tests file or folder path used by `#[path]` of a module generated at call site.
+/// 
+/// `#[path]` in an inline module is relative to a folder named after it, so path is relative to the folder implied
+/// by the inline modules enclosing the call site. Falls back to the full path in other blocks, like functions, or if
+/// that folder doesn't exist.
+pub(crate) fn get_module_path(path: &Path) -> String {
+
+    get_module_folder()
+        .and_then(|folder| relative_path(&folder, path))
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+
+}
+
+/// Get the tests_bin base folder as string
+#[inline(always)]
+pub(crate) fn get_tests_bin_base_folder() -> String {
+
+    match get_setting(TESTS_BIN_BASE_FOLDER_KEY) {
+        Some(base_folder) => base_folder,       // Return base folder from settings
+        None => String::from(TESTS_BIN_BASE_FOLDER)     // Key not found, return base folder.
+    }
+}
+
+/// Get the bench tests bin base folder as string
+#[inline(always)]
+pub(crate) fn get_tests_bin_bench_folder() -> String {
+
+    match get_setting(TESTS_BIN_BENCH_FOLDER_KEY) {
+        Some(base_folder) => base_folder,               // Return bench base folder from settings
+        None => String::from(TESTS_BIN_BENCH_FOLDER)    // Key not found, return bench base folder.
+    }
+}
+
+/// Get the doc tests bin base folder as string
+#[inline(always)]
+pub(crate) fn get_tests_bin_doc_folder() -> String {
+
+    match get_setting(TESTS_BIN_DOC_FOLDER_KEY) {
+        Some(base_folder) => base_folder,               // Return doc base folder from settings
+        None => String::from(TESTS_BIN_DOC_FOLDER)      // Key not found, return doc base folder.
+    }
+}
+
+/// Get the default cfg predicate of bench tests modules.
+#[inline(always)]
+pub(crate) fn get_tests_bin_bench_cfg() -> String {
+
+    match get_setting(TESTS_BIN_BENCH_CFG_KEY) {
+        Some(cfg) => cfg,                               // Return bench cfg predicate from settings
+        None => String::from(TESTS_BIN_BENCH_CFG)       // Key not found, return default bench cfg predicate.
+    }
+}
+
+/// Get the default cfg predicate of unit tests modules.
+#[inline(always)]
+pub(crate) fn get_tests_bin_cfg() -> String {
+
+    match get_setting(TESTS_BIN_CFG_KEY) {
+        Some(cfg) => cfg,                       // Return cfg predicate from settings
+        None => String::from(TESTS_BIN_CFG)     // Key not found, return default cfg predicate.
+    }
+}
+
+/// Get the prelude injected in modules of include mode.
+#[inline(always)]
+pub(crate) fn get_tests_bin_prelude() -> String {
+
+    get_setting(TESTS_BIN_PRELUDE_KEY).unwrap_or_default()  // No prelude by default.
+
+}
+
+/// Get the report level of mirror mode mismatches.
+#[inline(always)]
+pub(crate) fn get_tests_bin_mirror_report() -> String {
+
+    match get_setting(TESTS_BIN_MIRROR_REPORT_KEY) {
+        Some(report) => report,                     // Return report level from settings
+        None => String::from(REPORT_WARN)           // Key not found, return warnings.
+    }
+}
+
+/// Get the default policy confining linked paths to base folder.
+#[inline(always)]
+pub(crate) fn get_tests_bin_confine() -> String {
+
+    match get_setting(TESTS_BIN_CONFINE_KEY) {
+        Some(confine) => confine,                   // Return confinement policy from settings
+        None => String::from(CONFINE_STRICT)        // Key not found, return strict policy.
+    }
+}
+
+/// Get the path convention of `#[unit_tests]` without path.
+/// 
+/// `{ident}` is replaced by item identifier and `{kind}` by item kind keyword like `fn` or `struct`.
+#[inline(always)]
+pub(crate) fn get_tests_bin_convention() -> String {
+
+    match get_setting(TESTS_BIN_CONVENTION_KEY) {
+        Some(convention) => convention,                 // Return convention from settings
+        None => String::from(TESTS_BIN_CONVENTION)      // Key not found, return default convention.
+    }
+}
+
+/// Derive unit tests file path from item kind and identifier with convention.
+/// 
+/// Returns `None` if item has no identifier.
+pub(crate) fn derive_item_path(item: TokenStream) -> Option<String> {
+
+    let signature = parse_item_signature(item)?;
+    let ident = signature.ident?;
+
+    Some(get_tests_bin_convention().replace(CONVENTION_IDENT, &ident).replace(CONVENTION_KIND, signature.kind))
 
 }
 
 /// Can't use own crates to organize those. =(
 #[cfg(test)]
 mod tests {
-    use crate::config::{TESTS_BIN_BASE_FOLDER, get_tests_bin_base_folder, TESTS_BIN_BASE_FOLDER_KEY};
+    use crate::config::{TESTS_BIN_BASE_FOLDER, get_tests_bin_base_folder, TESTS_BIN_BASE_FOLDER_KEY, TESTS_BIN_CONVENTION, get_tests_bin_convention, 
+        TESTS_BIN_CFG, get_tests_bin_cfg, TESTS_BIN_BENCH_FOLDER, get_tests_bin_bench_folder, TESTS_BIN_BENCH_CFG, get_tests_bin_bench_cfg, 
+        TESTS_BIN_DOC_FOLDER, get_tests_bin_doc_folder, CONFINE_STRICT, get_tests_bin_confine, TestsRoot, TestsKind};
 
     const TESTS_BIN_CUSTOM_FOLDER : &str = "tests/custom";  // Used for custom test
 
@@ -140,6 +1136,70 @@ mod tests {
 
     }
 
+    /// Test default convention value
+    #[test]
+    fn get_tests_bin_convention_default() {
+        
+        let convention = get_tests_bin_convention();
+        assert_eq!(convention.as_str(), TESTS_BIN_CONVENTION, "Expected convention `{}`, got `{}`!", TESTS_BIN_CONVENTION, convention);
+
+    }
+
+    /// Test default bench folder and cfg predicate values
+    #[test]
+    fn get_tests_bin_bench_default() {
+        
+        let base_folder = get_tests_bin_bench_folder();
+        assert_eq!(base_folder.as_str(), TESTS_BIN_BENCH_FOLDER, "Expected bench base folder `{}`, got `{}`!", TESTS_BIN_BENCH_FOLDER, base_folder);
+
+        let cfg = get_tests_bin_bench_cfg();
+        assert_eq!(cfg.as_str(), TESTS_BIN_BENCH_CFG, "Expected bench cfg predicate `{}`, got `{}`!", TESTS_BIN_BENCH_CFG, cfg);
+
+    }
+
+    /// Test default doc folder value
+    #[test]
+    fn get_tests_bin_doc_folder_default() {
+        
+        let base_folder = get_tests_bin_doc_folder();
+        assert_eq!(base_folder.as_str(), TESTS_BIN_DOC_FOLDER, "Expected doc base folder `{}`, got `{}`!", TESTS_BIN_DOC_FOLDER, base_folder);
+
+    }
+
+    /// Test default cfg predicate value
+    #[test]
+    fn get_tests_bin_cfg_default() {
+        
+        let cfg = get_tests_bin_cfg();
+        assert_eq!(cfg.as_str(), TESTS_BIN_CFG, "Expected cfg predicate `{}`, got `{}`!", TESTS_BIN_CFG, cfg);
+
+    }
+
+    /// Test default confinement policy value
+    #[test]
+    fn get_tests_bin_confine_default() {
+        
+        let confine = get_tests_bin_confine();
+        assert_eq!(confine.as_str(), CONFINE_STRICT, "Expected confinement policy `{}`, got `{}`!", CONFINE_STRICT, confine);
+
+    }
+
+    /// Test named roots are only found when declared and use their defaults
+    #[test]
+    fn tests_root_named() {
+
+        assert!(TestsRoot::named(TestsKind::Unit, "totoro").is_none(), "Undeclared root should not be found!");
+
+        // Declare root folder in env
+        std::env::set_var("tests_bin-root-golden-folder", "tests/golden");
+
+        let root = TestsRoot::named(TestsKind::Unit, "golden").unwrap();
+        assert_eq!(root.folder.as_str(), "tests/golden", "Expected root folder `tests/golden`, got `{}`!", root.folder);
+        assert_eq!(root.cfg.as_str(), TESTS_BIN_CFG, "Expected default cfg `{}`, got `{}`!", TESTS_BIN_CFG, root.cfg);
+        assert_eq!(root.prefix.as_str(), "golden", "Expected root name as prefix, got `{}`!", root.prefix);
+
+    }
+
     /// Test custom folder value
     #[test]
     #[ignore = "Will fail default test when running in multiple thread."]
@@ -1,16 +1,286 @@
+use std::fmt;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
 
 /// Enumeration of possible tests_bin errors.
 pub enum TestsBinErrors {
 
     /// Happens when attributes macros parameters are incorrects.
-    IncorrectParameters
+    IncorrectParameters,
+
+    /// Happens when parameters are given to a macro without parameters, like `unit_tests_auto!()`.
+    UnexpectedParameters,
+
+    /// Happens when a parameter isn't preceded by a comma `,`.
+    MissingSeparator,
+
+    /// Happens when more than a path and a module name are given.
+    TooManyParameters,
+
+    /// Happens when doc tests parameter isn't a single `.md` or `.rs` path.
+    IncorrectDocParameters,
+
+    /// Happens when a key parameter isn't known. Contains key and known keys.
+    UnknownParameter(String, Vec<&'static str>),
+
+    /// Happens when `attrs(...)` doesn't contain attributes.
+    IncorrectAttributes,
+
+    /// Happens when a key parameter is given more than once.
+    DuplicateParameter(String),
+
+    /// Happens when the mode isn't `module` or `include`.
+    IncorrectMode(String),
+
+    /// Happens when the cfg predicate isn't valid.
+    IncorrectCfg(String),
+
+    /// Happens when the include mode prelude can't be parsed.
+    IncorrectPrelude(String),
+
+    /// Happens when a parameter literal isn't a string, like numbers or byte strings.
+    IncorrectLiteral(String),
+
+    /// Happens when a macro other than `concat!` or `env!` is given as parameter.
+    UnsupportedMacro(String),
+
+    /// Happens when the env variable of `env!` isn't set.
+    EnvVarNotSet(String),
+
+    /// Happens when no path literal is given.
+    MissingPath,
+
+    /// Happens when the module name given isn't a valid identifier.
+    IncorrectModuleName(String),
+
+    /// Happens when `CARGO_MANIFEST_DIR` isn't set and project root can't be found.
+    ProjectRootNotFound,
+
+    /// Happens when `unit_tests_auto!()` is used without the builder settings of `tests_bin_build` in `OUT_DIR`.
+    ModuleTreeNotGenerated,
+
+    /// Happens when the tests file doesn't exist in tests bin folder.
+    FileNotFound { path : String, folder : String, suggestions : Vec<String> },
+
+    /// Happens when a glob pattern doesn't match any tests file.
+    NoGlobMatch { pattern : String, folder : String },
+
+    /// Happens when `#[unit_tests]` has no path and item has no identifier to derive it.
+    PathNotDerivable,
+
+    /// Happens when an item kind can't have a tests module. Contains kind if recognized.
+    UnsupportedItem(Option<String>),
+
+    /// Happens when a module name is given with a glob pattern.
+    GlobModuleName,
+
+    /// Happens when the folder to link doesn't exist in tests bin folder.
+    FolderNotFound { path : String, folder : String },
+
+    /// Happens when the confinement policy isn't `strict`, `lexical` or `none`.
+    IncorrectConfine(String),
+
+    /// Happens when a linked path leaves tests bin folder, like with `..` or an absolute path.
+    PathOutsideFolder { path : String, folder : String },
+
+    /// Happens when a symbolic link of linked path leads outside tests bin folder.
+    SymlinkOutsideFolder { path : String, folder : String },
+
+    /// Happens when the named root isn't declared in settings.
+    RootNotDeclared(String),
+
+    /// Happens when the fixtures folder doesn't exist in tests bin folder.
+    FixturesNotFound { path : String, folder : String },
+
+    /// Happens when the folder to link has no tests file.
+    EmptyFolder { path : String, folder : String },
+
+    /// Happens when a file and a sub folder of linked folder generate the same module name.
+    DuplicateModuleName { name : String, folder : String },
+
+    /// Happens when the mirror report level isn't `warn`, `error` or `none`.
+    IncorrectMirrorReport(String),
+
+    /// Happens when a mirror mode mismatch is reported as an error.
+    MirrorMismatch(TestsBinWarnings),
+
+    /// Happens when a settings file sets `tests_bin` settings with unsupported TOML syntax.
+    UnsupportedSettings { file : String, line : String },
 
 }
 
-impl ToString for TestsBinErrors{
-    fn to_string(&self) -> String {
+impl fmt::Display for TestsBinErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TestsBinErrors::IncorrectParameters => String::from("Incorrect parameters! Should be \"path\" with optional \"module_name\" separated by comma `,`."),
+            TestsBinErrors::IncorrectParameters => write!(f, "Incorrect parameters! Should be \"path\" with optional \"module_name\", or `key = \"value\"` pairs of `path`, `name`, `dir`, `mode`, `fixtures`, `confine` and `root` with `attrs(...)` and `cfg(...)`, separated by comma `,`. See the syntax of `unit__tests!`."),
+            TestsBinErrors::UnexpectedParameters => write!(f, "Incorrect parameters! This macro takes no parameters."),
+            TestsBinErrors::MissingSeparator => write!(f, "Incorrect parameters! Missing comma `,` separator before this parameter."),
+            TestsBinErrors::TooManyParameters => write!(f, "Incorrect parameters! Unexpected parameter after \"module_name\"."),
+            TestsBinErrors::IncorrectDocParameters => write!(f, "Incorrect parameters! Should be \"path\" of a `.md` or `.rs` file."),
+            TestsBinErrors::UnknownParameter(key, keys) => write!(f, "Incorrect parameters! Unknown parameter `{}`, should be one of `{}`.", key, keys.join("`, `")),
+            TestsBinErrors::IncorrectAttributes => write!(f, "Incorrect parameters! `attrs` should be attributes separated by comma `,` like `attrs(allow(dead_code), ignore)`."),
+            TestsBinErrors::DuplicateParameter(key) => write!(f, "Incorrect parameters! `{}` is given more than once.", key),
+            TestsBinErrors::IncorrectMode(mode) => write!(f, "Incorrect mode `{}`! Should be \"module\" or \"include\".", mode),
+            TestsBinErrors::IncorrectCfg(cfg) => write!(f, "Incorrect cfg predicate `{}`! Should be like `test`, `feature = \"name\"`, `all(...)`, `any(...)` or `not(...)`.", cfg),
+            TestsBinErrors::IncorrectPrelude(prelude) => write!(f, "Incorrect prelude `{}`! Should be valid Rust tokens.", prelude),
+            TestsBinErrors::IncorrectLiteral(literal) => write!(f, "Incorrect parameter `{}`! Should be a string literal like \"path.rs\", `concat!(...)` or `env!(\"VAR\")`.", literal),
+            TestsBinErrors::UnsupportedMacro(name) => write!(f, "Unsupported macro `{}!`! Should be `concat!` or `env!`.", name),
+            TestsBinErrors::EnvVarNotSet(name) => write!(f, "Env variable `{}` not set!", name),
+            TestsBinErrors::MissingPath => write!(f, "Incorrect parameters! Missing \"path\" parameter."),
+            TestsBinErrors::IncorrectModuleName(name) => write!(f, "Incorrect module name `{}`! Should be a valid identifier.", name),
+            TestsBinErrors::ModuleTreeNotGenerated => write!(f, "Module tree not generated! `tests_bin_build::generate()` should be called from `build.rs`."),
+            TestsBinErrors::ProjectRootNotFound => write!(f, "Project root not found! Env variable `CARGO_MANIFEST_DIR` or `TESTS_BIN_ROOT` should be set."),
+            TestsBinErrors::FileNotFound { path, folder, suggestions } => {
+                write!(f, "Tests file `{}` not found in `{}`!", path, folder)?;
+                if !suggestions.is_empty() {
+                    write!(f, " Did you mean `{}`?", suggestions.join("`, `"))?;
+                }
+                Ok(())
+            },
+            TestsBinErrors::NoGlobMatch { pattern, folder } => write!(f, "Glob pattern `{}` matches no tests file in `{}`!", pattern, folder),
+            TestsBinErrors::FolderNotFound { path, folder } => write!(f, "Tests folder `{}` not found in `{}`!", path, folder),
+            TestsBinErrors::IncorrectConfine(confine) => write!(f, "Incorrect confine `{}`! Should be \"strict\", \"lexical\" or \"none\".", confine),
+            TestsBinErrors::PathOutsideFolder { path, folder } => write!(f, "Tests path `{}` is outside of `{}`! Use `confine = \"none\"` to allow it.", path, folder),
+            TestsBinErrors::SymlinkOutsideFolder { path, folder } => write!(f, "Tests path `{}` is a symbolic link leading outside of `{}`! Use `confine = \"lexical\"` to follow it.", path, folder),
+            TestsBinErrors::RootNotDeclared(root) => write!(f, "Tests root `{}` not declared! Should be declared with `root-{}-folder` setting.", root, root),
+            TestsBinErrors::FixturesNotFound { path, folder } => write!(f, "Fixtures folder `{}` not found in `{}`!", path, folder),
+            TestsBinErrors::EmptyFolder { path, folder } => write!(f, "Tests folder `{}` in `{}` contains no tests file!", path, folder),
+            TestsBinErrors::DuplicateModuleName { name, folder } => write!(f, "Module `{}` is defined by both a file and a folder in `{}`!", name, folder),
+            TestsBinErrors::IncorrectMirrorReport(report) => write!(f, "Incorrect mirror report `{}`! Should be \"warn\", \"error\" or \"none\".", report),
+            TestsBinErrors::MirrorMismatch(mismatch) => write!(f, "{}", mismatch),
+            TestsBinErrors::UnsupportedSettings { file, line } => write!(f, "Unsupported settings `{}` in `{}`! Should be `key = \"value\"` pairs under a `[...tests_bin]` table header.", line, file),
+            TestsBinErrors::PathNotDerivable => write!(f, "Can't derive tests path from an item without identifier! Should be given like (\"path.rs\")."),
+            TestsBinErrors::UnsupportedItem(kind) => {
+                match kind {
+                    Some(kind) => write!(f, "Unsupported item `{}`!", kind)?,
+                    None => write!(f, "Unsupported item!")?,
+                }
+                write!(f, " Should be a `fn`, `struct`, `enum`, `union`, `trait`, `impl`, `type`, `const`, `static`, `mod`, `macro_rules!`, `extern` block or `extern crate`.")
+            },
+            TestsBinErrors::GlobModuleName => write!(f, "Incorrect parameters! \"module_name\" can't be used with a glob pattern since names are generated from each file path."),
         }
     }
-}
\ No newline at end of file
+}
+
+/// tests_bin error with the span of the token that caused it.
+pub(crate) struct TestsBinError {
+    pub span : Span,
+    pub error : TestsBinErrors,
+}
+
+impl TestsBinError {
+
+    /// Create a new error pointing at span.
+    pub fn new(span : Span, error : TestsBinErrors) -> TestsBinError {
+        TestsBinError { span, error }
+    }
+
+    /// Convert error into a `compile_error!{"..."}` tokens pointing at error span.
+    pub fn to_compile_error(&self) -> TokenStream {
+
+        let mut punct = Punct::new('!', Spacing::Alone);
+        punct.set_span(self.span);
+
+        let mut message = Literal::string(self.error.to_string().as_str());
+        message.set_span(self.span);
+
+        let mut group = Group::new(Delimiter::Brace, TokenStream::from(TokenTree::Literal(message)));
+        group.set_span(self.span);
+
+        TokenStream::from_iter([
+            TokenTree::Ident(Ident::new("compile_error", self.span)),
+            TokenTree::Punct(punct),
+            TokenTree::Group(group),
+        ])
+
+    }
+
+}
+
+/// Convert all errors into `compile_error!` tokens so they are reported together.
+pub(crate) fn to_compile_errors(errors : Vec<TestsBinError>) -> TokenStream {
+
+    let mut content = TokenStream::new();
+
+    for error in errors {
+        content.extend(error.to_compile_error());
+    }
+
+    content
+
+}
+
+/// Enumeration of possible tests_bin warnings.
+pub enum TestsBinWarnings {
+
+    /// Happens in mirror mode when a source file has no mirror tests file.
+    MirrorNotFound { source : String, mirror : String },
+
+    /// Happens in mirror mode when a mirror tests file has no source file.
+    SourceNotFound { mirror : String, source : String },
+
+}
+
+impl fmt::Display for TestsBinWarnings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestsBinWarnings::MirrorNotFound { source, mirror } => write!(f, "Source file `{}` has no mirror tests file `{}`!", source, mirror),
+            TestsBinWarnings::SourceNotFound { mirror, source } => write!(f, "Mirror tests file `{}` has no source file `{}`!", mirror, source),
+        }
+    }
+}
+
+/// tests_bin warning with the span of the token that caused it.
+pub(crate) struct TestsBinWarning {
+    pub span : Span,
+    pub warning : TestsBinWarnings,
+}
+
+impl TestsBinWarning {
+
+    /// Create a new warning pointing at span.
+    pub fn new(span : Span, warning : TestsBinWarnings) -> TestsBinWarning {
+        TestsBinWarning { span, warning }
+    }
+
+    /// Convert warning into the use of a deprecated item pointing at warning span, since stable macros can't emit warnings.
+    pub fn to_compile_warning(&self) -> TokenStream {
+
+        let warning = format!("const _ : () = {{ #[deprecated(note = {})] struct TestsBinWarning; let _ = TestsBinWarning; }};", 
+            Literal::string(self.warning.to_string().as_str()));
+
+        set_span(warning.parse::<TokenStream>().unwrap(), self.span)
+
+    }
+
+}
+
+/// Convert all warnings into deprecated items uses so they are reported together.
+pub(crate) fn to_compile_warnings(warnings : &[TestsBinWarning]) -> TokenStream {
+
+    let mut content = TokenStream::new();
+
+    for warning in warnings {
+        content.extend(warning.to_compile_warning());
+    }
+
+    content
+
+}
+
+/// Set span of every token of stream, including tokens of groups.
+fn set_span(stream : TokenStream, span : Span) -> TokenStream {
+
+    stream.into_iter().map(|token| match token {
+        TokenTree::Group(group) => {
+            let mut respanned = Group::new(group.delimiter(), set_span(group.stream(), span));
+            respanned.set_span(span);
+            TokenTree::Group(respanned)
+        },
+        mut token => {
+            token.set_span(span);
+            token
+        },
+    }).collect()
+
+}
@@ -0,0 +1,453 @@
+use std::path::{Path, PathBuf, Component};
+
+pub(crate) use tests_bin_settings::relative_path;
+
+// Contants
+const GLOB_CHARACTERS : [char; 2] = ['*', '?'];                       // Characters that make a path a glob pattern
+const GLOB_RECURSIVE : &str = "**";                                 // Glob segment matching any number of folders
+const MAX_SUGGESTIONS : usize = 3;                                  // Maximum count of suggestions given for a missing file
+const MIN_SUGGESTION_DISTANCE : usize = 3;                          // Minimum edit distance accepted for a suggestion
+const MOD_RS_FILES : [&str; 3] = ["mod", "lib", "main"];           // Stems of files whose inline modules folders are next to them
+
+/// Get files relative paths of folder and its sub folders, separated by `/` and sorted.
+///
+/// Unreadable folders are ignored.
+pub(crate) fn list_files(folder: &Path) -> Vec<String> {
+
+    let mut files : Vec<String> = Vec::new();
+    list_files_recursive(folder, "", &mut files);
+    files.sort();
+    files
+
+}
+
+/// Accumulate files relative paths of folder into files.
+fn list_files_recursive(folder: &Path, prefix: &str, files: &mut Vec<String>) {
+
+    if let Ok(entries) = std::fs::read_dir(folder) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let relative = format!("{}{}", prefix, name);
+
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => list_files_recursive(&entry.path(), format!("{}/", relative).as_str(), files),
+                Ok(_) => files.push(relative),
+                Err(_) => {},   // Ignore entries we can't read
+            }
+        }
+    }
+
+}
+
+/// Returns true if path is a glob pattern.
+pub(crate) fn is_glob_pattern(path: &str) -> bool {
+
+    path.contains(GLOB_CHARACTERS)
+
+}
+
+/// Get files relative paths of folder matching glob pattern, sorted.
+///
+/// `*` and `?` match any characters and one character within a folder or file name, 
+/// `**` match any number of folders.
+pub(crate) fn glob_files(folder: &Path, pattern: &str) -> Vec<String> {
+
+    list_files(folder).into_iter().filter(|file| glob_match(pattern, file)).collect()
+
+}
+
+/// Match source files with mirror files of the same relative path.
+///
+/// Returns mirror files with a source, source files without mirror and mirror files without source, in given order.
+pub(crate) fn match_mirrors(sources: &[String], mirrors: &[String]) -> (Vec<String>, Vec<String>, Vec<String>) {
+
+    let matched = mirrors.iter().filter(|mirror| sources.contains(mirror)).cloned().collect();
+    let missing = sources.iter().filter(|source| !mirrors.contains(source)).cloned().collect();
+    let orphans = mirrors.iter().filter(|mirror| !sources.contains(mirror)).cloned().collect();
+
+    (matched, missing, orphans)
+
+}
+
+/// Returns true if relative path matches glob pattern.
+pub(crate) fn glob_match(pattern: &str, path: &str) -> bool {
+
+    let pattern : Vec<&str> = pattern.split('/').collect();
+    let path : Vec<&str> = path.split('/').collect();
+
+    glob_match_segments(&pattern, &path)
+
+}
+
+/// Match path segments against pattern segments.
+fn glob_match_segments(pattern: &[&str], path: &[&str]) -> bool {
+
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(&GLOB_RECURSIVE) => glob_match_segments(&pattern[1..], path) || (!path.is_empty() && glob_match_segments(pattern, &path[1..])),
+        Some(segment) => !path.is_empty() && wildcard_match(&segment.chars().collect::<Vec<char>>(), &path[0].chars().collect::<Vec<char>>()) && glob_match_segments(&pattern[1..], &path[1..]),
+    }
+
+}
+
+/// Match a folder or file name against a pattern with `*` and `?` wildcards.
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => wildcard_match(&pattern[1..], name) || (!name.is_empty() && wildcard_match(pattern, &name[1..])),
+        Some('?') => !name.is_empty() && wildcard_match(&pattern[1..], &name[1..]),
+        Some(c) => name.first() == Some(c) && wildcard_match(&pattern[1..], &name[1..]),
+    }
+
+}
+
+/// Get the nearest files of path in folder, nearest first.
+///
+/// Only files within an edit distance of a third of path length (minimum 3) are suggested.
+pub(crate) fn suggest_files(folder: &Path, path: &str) -> Vec<String> {
+
+    let max_distance = (path.chars().count() / 3).max(MIN_SUGGESTION_DISTANCE);
+
+    let mut suggestions : Vec<(usize, String)> = list_files(folder).into_iter()
+        .map(|file| (edit_distance(path, file.as_str()), file))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+
+    // Sort is stable and files are already sorted, so equal distances stay in alphabetical order.
+    suggestions.sort_by_key(|(distance, _)| *distance);
+    suggestions.into_iter().take(MAX_SUGGESTIONS).map(|(_, file)| file).collect()
+
+}
+
+/// Normalize path by resolving `.` and `..` without accessing the file system.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {},
+            Component::ParentDir => { normalized.pop(); },
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+
+}
+
+/// Returns true if path relative to folder stays in folder once normalized, like `ponyo/../ham.rs`.
+pub(crate) fn is_lexically_inside(folder: &Path, path: &str) -> bool {
+
+    normalize_path(&folder.join(path)).starts_with(normalize_path(folder))
+
+}
+
+/// Returns true if path relative to folder stays in folder once symbolic links are resolved.
+///
+/// Paths that don't exist are considered inside since they have no link to follow.
+pub(crate) fn is_canonically_inside(folder: &Path, path: &str) -> bool {
+
+    match (folder.canonicalize(), folder.join(path).canonicalize()) {
+        (Ok(folder), Ok(path)) => path.starts_with(folder),
+        _ => true,
+    }
+
+}
+
+/// Brace opened in source code.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BraceScope {
+    /// Block of an inline module, like `mod name { ... }`.
+    Module(String),
+    /// Block of an inline module with a `#[path]` attribute.
+    PathModule(String),
+    /// Any other block, like a function body or an `impl` block, at its position in source code.
+    Block(usize),
+}
+
+/// Braces of source code, lexed once so that scopes of every call site of a file are found without reading it again.
+#[derive(Debug, Clone)]
+pub(crate) struct SourceBraces {
+    /// Position of the start of each line, as a characters count.
+    lines: Vec<usize>,
+    /// Position of each brace, with its scope if opened or `None` if closed.
+    braces: Vec<(usize, Option<BraceScope>)>,
+    /// Characters count of source code.
+    len: usize,
+}
+
+impl SourceBraces {
+    /// Lex braces of source code, ignoring braces of comments, strings and characters literals.
+    pub(crate) fn new(source: &str) -> SourceBraces {
+
+        let lines = std::iter::once(0).chain(source.split('\n').scan(0, |start, line| { *start += line.chars().count() + 1; Some(*start) })).collect();
+        let source : Vec<char> = source.chars().collect();
+
+        SourceBraces { lines, braces: lex_braces(&source), len: source.len() }
+
+    }
+
+    /// Get braces opened before line and column of source code, both starting at 1, from outermost to innermost.
+    ///
+    /// Returns `None` if position is after the end.
+    pub(crate) fn get_scopes(&self, line: usize, column: usize) -> Option<Vec<BraceScope>> {
+
+        let position = self.lines.get(line.checked_sub(1)?)? + column.checked_sub(1)?;
+        if position > self.len {
+            return None;
+        }
+
+        let mut scopes : Vec<BraceScope> = Vec::new();
+        for (_, brace) in self.braces.iter().take_while(|(brace_position, _)| *brace_position < position) {
+            match brace {
+                Some(scope) => scopes.push(scope.clone()),
+                None => { scopes.pop(); },
+            }
+        }
+
+        Some(scopes)
+
+    }
+}
+
+/// Get position of each brace of source code, with its scope if opened or `None` if closed.
+fn lex_braces(source: &[char]) -> Vec<(usize, Option<BraceScope>)> {
+
+    let mut braces : Vec<(usize, Option<BraceScope>)> = Vec::new();
+    let mut tokens : Vec<String> = Vec::new();      // Tokens since the last item, to find inline modules
+    let mut path_attribute = false;                 // `#[path` found since the last item
+    let mut i = 0;
+
+    while i < source.len() {
+        match (source[i], source.get(i + 1).copied()) {
+            // Line comment
+            ('/', Some('/')) => while i < source.len() && source[i] != '\n' { i += 1; },
+
+            // Block comment, which can be nested
+            ('/', Some('*')) => {
+                let mut comments = 0;
+                while i < source.len() {
+                    match (source[i], source.get(i + 1).copied()) {
+                        ('/', Some('*')) => { comments += 1; i += 2; },
+                        ('*', Some('/')) => { comments -= 1; i += 2; if comments == 0 { break; } },
+                        _ => i += 1,
+                    }
+                }
+                continue;
+            },
+
+            // String, skipping escaped characters
+            ('"', _) => {
+                i += 1;
+                while i < source.len() && source[i] != '"' {
+                    i += if source[i] == '\\' { 2 } else { 1 };
+                }
+                tokens.push(String::from("\""));
+            },
+
+            // Character literal, or lifetime which is skipped as an identifier
+            ('\'', Some('\\')) => {
+                i += 3;
+                while i < source.len() && source[i] != '\'' { i += 1; }
+                tokens.push(String::from("'"));
+            },
+            ('\'', Some(_)) if source.get(i + 2) == Some(&'\'') => {
+                i += 2;
+                tokens.push(String::from("'"));
+            },
+
+            // Identifier, which can start a raw string like `r#"..."#` or `br"..."`
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < source.len() && (source[i].is_alphanumeric() || source[i] == '_') { i += 1; }
+                let word : String = source[start..i].iter().collect();
+                if matches!(word.as_str(), "r" | "br" | "cr") {
+                    let hashes = source[i..].iter().take_while(|c| **c == '#').count();
+                    if source.get(i + hashes) == Some(&'"') {
+                        i += hashes + 1;
+                        while i < source.len() && !(source[i] == '"' && source[i + 1..].iter().take(hashes).filter(|c| **c == '#').count() == hashes) { i += 1; }
+                        i += hashes + 1;
+                        tokens.push(String::from("\""));
+                        continue;
+                    }
+                }
+                if word == "path" && matches!(tokens.as_slice(), [.., hash, bracket] if hash == "#" && bracket == "[") {
+                    path_attribute = true;
+                }
+                tokens.push(word);
+                continue;
+            },
+
+            ('{', _) => {
+                braces.push((i, Some(match tokens.as_slice() {
+                    [.., keyword, name] if keyword == "mod" && path_attribute => BraceScope::PathModule(name.clone()),
+                    [.., keyword, name] if keyword == "mod" => BraceScope::Module(name.clone()),
+                    _ => BraceScope::Block(i),
+                })));
+                tokens.clear();
+                path_attribute = false;
+            },
+            ('}', _) | (';', _) => {
+                if source[i] == '}' {
+                    braces.push((i, None));
+                }
+                tokens.clear();
+                path_attribute = false;
+            },
+            (c, _) if c.is_whitespace() => {},
+            (c, _) => tokens.push(c.to_string()),
+        }
+        i += 1;
+    }
+
+    braces
+
+}
+
+/// Get folder `#[path]` attributes are relative to in braces of a source file.
+///
+/// At file level, it is the folder of the source file. In inline modules, it is followed by the module names, after
+/// the file name without extension unless the file is a `mod.rs` or the crate root, like `src/ponyo/inner/` for
+/// `mod inner { ... }` in `src/ponyo.rs`. Returns `None` in other blocks, in inline modules with `#[path]`, or if the
+/// folder doesn't exist, since rustc can't resolve `..` through missing folders.
+pub(crate) fn get_implied_folder(file: &Path, scopes: &[BraceScope], crate_name: Option<&str>) -> Option<PathBuf> {
+
+    let mut folder = file.parent()?.to_path_buf();
+    if scopes.is_empty() {
+        return Some(folder);
+    }
+
+    let stem = file.file_stem()?.to_string_lossy().to_string();
+    if !MOD_RS_FILES.contains(&stem.as_str()) && crate_name != Some(stem.as_str()) {
+        folder.push(stem);
+    }
+
+    for scope in scopes {
+        match scope {
+            BraceScope::Module(name) => folder.push(name),
+            _ => return None,
+        }
+    }
+
+    folder.is_dir().then_some(folder)
+
+}
+
+/// Levenshtein edit distance between 2 strings.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+
+    let b : Vec<char> = b.chars().collect();
+
+    // Distance of previous row, starting with distance from empty string.
+    let mut previous : Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current : Vec<usize> = vec![i + 1; b.len() + 1];
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::files::{edit_distance, get_implied_folder, BraceScope, SourceBraces, glob_match, match_mirrors, is_lexically_inside};
+
+    /// Test edit distance of identical, empty and different strings
+    #[test]
+    fn edit_distance_values() {
+
+        assert_eq!(edit_distance("ponyo/ham.rs", "ponyo/ham.rs"), 0, "Identical strings should have distance 0!");
+        assert_eq!(edit_distance("", "ham.rs"), 6, "Distance from empty string should be length!");
+        assert_eq!(edit_distance("ponyo/hamm.rs", "ponyo/ham.rs"), 1, "Extra character should have distance 1!");
+        assert_eq!(edit_distance("ponyo/jam.rs", "ponyo/ham.rs"), 1, "Substitution should have distance 1!");
+        assert_eq!(edit_distance("kitten", "sitting"), 3, "Expected classic kitten/sitting distance of 3!");
+
+    }
+
+    /// Test glob patterns with `*`, `?` and `**`
+    #[test]
+    fn glob_match_patterns() {
+
+        assert!(glob_match("*.rs", "add.rs"), "`*.rs` should match `add.rs`!");
+        assert!(!glob_match("*.rs", "math/add.rs"), "`*.rs` should not match sub folders!");
+        assert!(glob_match("math/*.rs", "math/add.rs"), "`math/*.rs` should match `math/add.rs`!");
+        assert!(glob_match("math/**/*.rs", "math/add.rs"), "`**` should match zero folder!");
+        assert!(glob_match("math/**/*.rs", "math/int/signed/add.rs"), "`**` should match many folders!");
+        assert!(!glob_match("math/**/*.rs", "physics/add.rs"), "`math/**/*.rs` should not match `physics/add.rs`!");
+        assert!(glob_match("math/ad?.rs", "math/add.rs"), "`?` should match one character!");
+        assert!(!glob_match("math/*.rs", "math/add.txt"), "Extension should be matched!");
+
+    }
+
+
+    /// Test source files matched with mirror files, missing mirrors and orphan mirrors
+    #[test]
+    fn match_mirrors_files() {
+
+        let sources = [String::from("lib.rs"), String::from("ponyo/ham.rs"), String::from("sosuke.rs")];
+        let mirrors = [String::from("ponyo/ham.rs"), String::from("sosuke.rs"), String::from("sosuke/boat.rs")];
+
+        let (matched, missing, orphans) = match_mirrors(&sources, &mirrors);
+        assert_eq!(matched, ["ponyo/ham.rs", "sosuke.rs"], "Mirrors with same path as a source should be matched!");
+        assert_eq!(missing, ["lib.rs"], "Sources without mirror should be missing!");
+        assert_eq!(orphans, ["sosuke/boat.rs"], "Mirrors without source should be orphans!");
+
+    }
+
+    /// Test brace scopes ignoring braces of comments and literals
+    #[test]
+    fn get_brace_scopes_positions() {
+
+        let source = "mod ponyo {\n    // }\n    /* } /* } */ } */\n    const HAM : &str = \"}\";\n    const C : char = '}';\n    const R : &str = r#\"}\"#;\n    fn f<'a>() {}\n    #[ham]\n}\n#[ham]\n#[path = \"sosuke\"]\npub mod sosuke {\n    fn boat() {\n        #[ham]\n    }\n}\n";
+        let braces = SourceBraces::new(source);
+        let ponyo = BraceScope::Module(String::from("ponyo"));
+        assert_eq!(braces.get_scopes(1, 1), Some(vec![]), "Start of source should be at file level!");
+        assert_eq!(braces.get_scopes(8, 5), Some(vec![ponyo]), "Braces of comments and literals should be ignored!");
+        assert_eq!(braces.get_scopes(10, 1), Some(vec![]), "Closed module should be at file level!");
+        assert_eq!(braces.get_scopes(14, 9), Some(vec![BraceScope::PathModule(String::from("sosuke")), BraceScope::Block(214)]), 
+            "Function in module with `#[path]` should be named after position!");
+        assert_eq!(braces.get_scopes(30, 1), None, "Position after the end should be None!");
+
+    }
+
+    /// Test folders implied by inline modules of crate roots, `mod.rs` and other files
+    #[test]
+    fn get_implied_folder_scopes() {
+
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let integration = [BraceScope::Module(String::from("integration"))];
+
+        assert_eq!(get_implied_folder(&manifest_dir.join("src/lib.rs"), &[], None), Some(manifest_dir.join("src")), "File level should be the folder of the file!");
+        assert_eq!(get_implied_folder(&manifest_dir.join("tests/tests.rs"), &integration, Some("tests")), Some(manifest_dir.join("tests/integration")), 
+            "Inline module of crate root should be next to it!");
+        assert_eq!(get_implied_folder(&manifest_dir.join("tests/tests.rs"), &integration, None), None, "Missing folder of other files should be None!");
+        assert_eq!(get_implied_folder(&manifest_dir.join("tests/mod.rs"), &[BraceScope::Block(0)], None), None, "Other blocks should be None!");
+
+    }
+
+    /// Test paths escaping folder with `..` or absolute paths
+    #[test]
+    fn is_lexically_inside_paths() {
+
+        let folder = Path::new("/ponyo/tests/unit");
+        assert!(is_lexically_inside(folder, "ham.rs"), "`ham.rs` should be inside!");
+        assert!(is_lexically_inside(folder, "ponyo/../ham.rs"), "`ponyo/../ham.rs` should be inside!");
+        assert!(!is_lexically_inside(folder, "../../src/secret.rs"), "`../../src/secret.rs` should be outside!");
+        assert!(!is_lexically_inside(folder, "/etc/passwd"), "Absolute paths should be outside!");
+        assert!(!is_lexically_inside(folder, "../unit2/ham.rs"), "Sibling folder with same prefix should be outside!");
+
+    }
+
+}
@@ -0,0 +1,282 @@
+use std::iter::Peekable;
+
+use proc_macro::{TokenStream, TokenTree, Delimiter, Spacing, Group, token_stream::IntoIter};
+
+// Contants
+const UNSUPPORTED_KINDS : [&str; 1] = ["use"];                      // Item kinds that can't have a tests module
+
+/// Kind and identifier of an [item](https://doc.rust-lang.org/reference/items.html).
+pub(crate) struct ItemSignature {
+    /// Item kind keyword like `fn`, `struct` or `macro_rules`.
+    pub kind : &'static str,
+
+    /// Item identifier. `None` for items without one like `impl` and `extern` blocks.
+    pub ident : Option<String>,
+
+    /// Item name used for module names. Same as identifier, `Trait_for_Type` or `Type` for `impl` blocks 
+    /// and ABI for `extern` blocks.
+    pub name : Option<String>,
+}
+
+/// Parse item kind and identifier, skipping attributes, visibility and qualifiers.
+///
+/// Returns `None` if item kind isn't recognized.
+pub(crate) fn parse_item_signature(item: TokenStream) -> Option<ItemSignature> {
+
+    let mut tokens = item.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            // Skip outer attributes `#[...]`
+            TokenTree::Punct(punct) if punct.as_char() == '#' => { tokens.next(); },
+
+            TokenTree::Ident(ident) => {
+                let kind : &'static str = match ident.to_string().as_str() {
+                    // Skip visibility with its optional `(crate)`, `(super)`, `(in path)`
+                    "pub" => {
+                        if let Some(TokenTree::Group(group)) = tokens.peek() {
+                            if group.delimiter() == Delimiter::Parenthesis {
+                                tokens.next();
+                            }
+                        }
+                        continue;
+                    },
+
+                    // Skip qualifiers
+                    "async" | "unsafe" | "default" | "auto" => continue,
+
+                    // `extern "ABI" fn`, `extern crate` or `extern` block
+                    "extern" => {
+                        // Extern blocks are named after their ABI like `extern "C"`
+                        let abi = match tokens.peek() {
+                            Some(TokenTree::Literal(abi)) => Some(abi.to_string().trim_matches('"').to_string()),
+                            _ => None,
+                        };
+                        if abi.is_some() {
+                            tokens.next();
+                        }
+                        match tokens.peek() {
+                            Some(TokenTree::Group(_)) => return Some(ItemSignature { kind: "extern", ident: None, name: abi }),
+                            Some(TokenTree::Ident(next)) if next.to_string() == "crate" => { tokens.next(); "extern_crate" },
+                            _ => continue,
+                        }
+                    },
+
+                    // `const fn` qualifier or const item
+                    "const" => match tokens.peek() {
+                        Some(TokenTree::Ident(next)) if matches!(next.to_string().as_str(), "fn" | "async" | "unsafe" | "extern") => continue,
+                        _ => "const",
+                    },
+
+                    // `macro_rules! name`
+                    "macro_rules" => {
+                        tokens.next();  // Skip `!`
+                        "macro_rules"
+                    },
+
+                    "static" => {
+                        if let Some(TokenTree::Ident(next)) = tokens.peek() {
+                            if next.to_string() == "mut" {
+                                tokens.next();
+                            }
+                        }
+                        "static"
+                    },
+
+                    "impl" => {
+                        let name = match parse_impl_names(tokens) {
+                            (Some(trait_name), Some(type_name)) => Some(format!("{}_for_{}", trait_name, type_name)),
+                            (Some(trait_name), None) => Some(trait_name),   // Types without name, like tuples and arrays
+                            (None, type_name) => type_name,
+                        };
+                        return Some(ItemSignature { kind: "impl", ident: None, name });
+                    },
+                    "use" => return Some(ItemSignature { kind: "use", ident: None, name: None }),
+                    "fn" => "fn",
+                    "struct" => "struct",
+                    "enum" => "enum",
+                    "union" => "union",
+                    "trait" => "trait",
+                    "type" => "type",
+                    "mod" => "mod",
+
+                    // Unknown item
+                    _ => return None,
+                };
+
+                // Identifier follows kind keyword
+                let ident = match tokens.next() {
+                    // Unnamed `const _` has no identifier
+                    Some(TokenTree::Ident(ident)) if ident.to_string() != "_" => Some(ident.to_string().trim_start_matches("r#").to_string()),
+                    _ => None,
+                };
+
+                return Some(ItemSignature { kind, name: ident.clone(), ident });
+            },
+
+            // Unknown item
+            _ => return None,
+        }
+    }
+
+    None
+
+}
+
+/// Returns true if a tests module can be linked with item kind.
+pub(crate) fn is_supported_kind(kind: &str) -> bool {
+
+    !UNSUPPORTED_KINDS.contains(&kind)
+
+}
+
+/// Parse `impl` block trait and type names from tokens following `impl`, like `Display` and `Point`.
+///
+/// Generics, lifetimes and paths are skipped so only the last identifier of trait and type are kept.
+/// Type is `None` if it has no identifier, like tuples.
+fn parse_impl_names(mut tokens: Peekable<IntoIter>) -> (Option<String>, Option<String>) {
+
+    let mut trait_name : Option<String> = None;
+    let mut type_name : Option<String> = None;
+    let mut depth = 0;              // Depth of generics `<...>`
+    let mut arrow = false;          // Previous token is `-` of `->`
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) => {
+                match punct.as_char() {
+                    '<' => depth += 1,
+                    '>' if !arrow => depth -= 1,
+                    // Skip lifetime identifier
+                    '\'' => { tokens.next(); },
+                    _ => {},
+                }
+                arrow = punct.as_char() == '-' && punct.spacing() == Spacing::Joint;
+            },
+            TokenTree::Ident(ident) if depth == 0 => match ident.to_string().as_str() {
+                "for" => trait_name = type_name.take(),
+                "where" => break,
+                "dyn" | "mut" | "const" | "unsafe" => {},
+                ident => type_name = Some(ident.trim_start_matches("r#").to_string()),
+            },
+            // Body of impl block
+            TokenTree::Group(group) if depth == 0 && group.delimiter() == Delimiter::Brace => break,
+            _ => {},
+        }
+    }
+
+    (trait_name, type_name)
+
+}
+
+/// Associated item of an `impl` block with the parameters of its tests helper attributes.
+pub(crate) struct ImplMethod {
+    /// Parameters of each helper attribute, empty if given without parentheses.
+    pub parameters : Vec<TokenStream>,
+
+    /// Associated item tokens, without helper attributes.
+    pub item : TokenStream,
+}
+
+/// Remove helper attributes like `#[unit_test_file(...)]` from associated items of an `impl` block.
+///
+/// Returns `impl` block type name, `impl` block without helper attributes and the associated items that had them.
+/// Returns `None` if item isn't an `impl` block or none of its associated items have helper attributes.
+pub(crate) fn split_impl_methods(item: TokenStream, helper: &str) -> Option<(String, TokenStream, Vec<ImplMethod>)> {
+
+    let signature = parse_item_signature(item.clone())?;
+    if signature.kind != "impl" {
+        return None;
+    }
+
+    // Type name follows `impl` keyword and body is the last token
+    let mut tokens : Vec<TokenTree> = item.into_iter().collect();
+    let start = tokens.iter().position(|token| matches!(token, TokenTree::Ident(ident) if ident.to_string() == "impl"))?;
+    let type_name = parse_impl_names(tokens[start + 1..].iter().cloned().collect::<TokenStream>().into_iter().peekable()).1?;
+
+    let body = match tokens.pop() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        _ => return None,
+    };
+
+    // Rebuild body without helper attributes
+    let mut methods : Vec<ImplMethod> = Vec::new();
+    let mut new_body = Group::new(Delimiter::Brace, split_body_methods(body.stream(), helper, &mut methods));
+    new_body.set_span(body.span());
+    tokens.push(TokenTree::Group(new_body));
+
+    match methods.is_empty() {
+        true => None,
+        false => Some((type_name, tokens.into_iter().collect(), methods)),
+    }
+
+}
+
+/// Remove helper attributes of `impl` block body associated items and accumulate items that had them into methods.
+fn split_body_methods(body: TokenStream, helper: &str, methods: &mut Vec<ImplMethod>) -> TokenStream {
+
+    let mut content = TokenStream::new();
+    let mut current : Vec<TokenTree> = Vec::new();          // Tokens of current associated item
+    let mut parameters : Vec<TokenStream> = Vec::new();     // Helper attributes parameters of current associated item
+    let mut tokens = body.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            // Outer attribute `#[...]`, removed if it's the helper attribute
+            TokenTree::Punct(punct) if punct.as_char() == '#' => match tokens.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket && is_helper_attribute(&group, helper) => {
+                    parameters.push(group.stream().into_iter().nth(1).map(|arguments| match arguments {
+                        TokenTree::Group(arguments) => arguments.stream(),
+                        arguments => TokenStream::from(arguments),
+                    }).unwrap_or_default());
+                },
+                Some(next) => current.extend([TokenTree::Punct(punct), next]),
+                None => current.push(TokenTree::Punct(punct)),
+            },
+
+            // Associated item ends with its body or `;`
+            TokenTree::Group(ref group) if group.delimiter() == Delimiter::Brace => {
+                current.push(token);
+                push_body_method(&mut content, &mut current, &mut parameters, methods);
+            },
+            TokenTree::Punct(ref punct) if punct.as_char() == ';' => {
+                current.push(token);
+                push_body_method(&mut content, &mut current, &mut parameters, methods);
+            },
+
+            _ => current.push(token),
+        }
+    }
+
+    // Incomplete trailing tokens are kept as is
+    push_body_method(&mut content, &mut current, &mut parameters, methods);
+
+    content
+
+}
+
+/// Push associated item tokens to content, and to methods if it had helper attributes.
+fn push_body_method(content: &mut TokenStream, current: &mut Vec<TokenTree>, parameters: &mut Vec<TokenStream>, methods: &mut Vec<ImplMethod>) {
+
+    let item : TokenStream = current.drain(..).collect();
+
+    if !parameters.is_empty() {
+        methods.push(ImplMethod { parameters: std::mem::take(parameters), item: item.clone() });
+    }
+
+    content.extend(item);
+
+}
+
+/// Returns true if attribute group content is helper attribute, like `unit_test_file(...)` or `unit_test_file`.
+fn is_helper_attribute(group: &Group, helper: &str) -> bool {
+
+    let mut tokens = group.stream().into_iter();
+
+    match (tokens.next(), tokens.next(), tokens.next()) {
+        (Some(TokenTree::Ident(ident)), None, None) => ident.to_string() == helper,
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(arguments)), None) => ident.to_string() == helper && arguments.delimiter() == Delimiter::Parenthesis,
+        _ => false,
+    }
+
+}
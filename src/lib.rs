@@ -3,25 +3,85 @@
 //! This [crate](https://doc.rust-lang.org/book/ch07-01-packages-and-crates.html) is aimed toward [Rustacean](https://rustaceans.org/) 
 //! who wish to have a bin folder where they can easily organize all their unit tests to clean their src folder. VSCode extension included! [Visit Wiki for more informations.](https://github.com/NickelAngeStudio/tests_bin/wiki)
 //! 
+//! Requires Rust 1.88 or newer since 2.0.0, which reads call site locations with `Span::file`, `line`, `column` and `local_file`.
+//! 
 //! # Usage
 //! 
 //! ```
 //! use tests_bin::{ unit__tests, unit_tests };
 //!
 //! // Will link a module to `tests/unit/global_tests.rs` 
-//! // with a module named `global_test_rs`.
+//! // with a module named `global_tests_rs`.
 //! unit__tests!("global_tests.rs");
 //!  
 //! // Will link a module to `tests/unit/add.rs` 
-//! // with a module named `pub_fn_add_usize`.
+//! // with a module named `tests_fn_add`.
 //! #[unit_tests("add.rs")]
 //! pub fn add(left: usize, right: usize) -> usize {
 //!     left + right
 //! }
 //! ```
+//! 
+//! # Configuration
+//! 
+//! Settings are read, in order of precedence, from :
+//! 1. Env keys like `tests_bin-folder`, usually set in `.cargo/config.toml` `[env]`.
+//! 2. `tests_bin.toml` next to `Cargo.toml`, with keys without `tests_bin-` prefix like `folder = "tests/unit"`.
+//! 3. `[package.metadata.tests_bin]` table of `Cargo.toml`, with the same keys as `tests_bin.toml`.
+//! 4. `[workspace.metadata.tests_bin]` table of the workspace root `Cargo.toml`, shared by all members.
+//! 5. Default values.
+//! 
+//! Only `key = "value"` lines under a table header are read. Inline tables like `tests_bin = { folder = "..." }`,
+//! dotted keys and multi-line strings setting `tests_bin` settings are reported as errors instead of being ignored.
+//! 
+//! Values can use the `{workspace}` placeholder, replaced by the workspace root folder relative to `Cargo.toml`,
+//! and the `{crate}` placeholder, replaced by the package name. A workspace can keep all its tests in one folder
+//! with `folder = "{workspace}/tests/unit/{crate}"`. The `{target}` placeholder is replaced by the binary name, or the
+//! crate name of other targets, so each target has its own tests with `folder = "tests/unit/{target}"`. Targets are
+//! compiled by separate processes, so a tests file linked from two targets is compiled in both. It is reported with a
+//! warning by `tests_bin_build`, which compares the literal paths linked by each target from its sources.
+//! 
+//! # Outside of cargo
+//! 
+//! Paths are relative to the project folder given by `CARGO_MANIFEST_DIR`. When built outside of cargo, like with Bazel,
+//! Buck or plain `rustc`, the project folder is given by the `TESTS_BIN_ROOT` env var, relative to the compiler working
+//! directory, or else is the nearest folder of the invoking source file that is the parent of a `src` folder or contains
+//! `tests_bin.toml` or `Cargo.toml`.
+//! 
+//! # Rebuild
+//! 
+//! Macros are expanded again when env settings or existing settings files change, without `cargo clean`. Linked files are tracked
+//! by the compiler, but stable macros can't track folders, so files added to a glob pattern, `dir` folder or mirror folders are only
+//! picked up with the `nightly` feature, which uses the unstable tracked path and env APIs. Creating a settings file
+//! still needs a clean build. `#[doc_tests]` can document methods, so on stable it only tracks its documentation file.
+//! 
+//! | Key | Default | Description |
+//! |-----|---------|-------------|
+//! | `folder` | `"tests/unit"` | Unit tests bin folder. |
+//! | `cfg` | `"test"` | Default cfg predicate of unit tests modules. |
+//! | `prefix` | `"tests"` | Prefix of unit tests module names generated from items. |
+//! | `convention` | `"{ident}.rs"` | Path convention of `#[unit_tests]` without path. |
+//! | `prelude` | `""` | Prelude of include mode modules. |
+//! | `fixtures` | Tests bin folder | Folder fixtures paths are relative to. |
+//! | `confine` | `"strict"` | Default policy confining paths to tests bin folder. |
+//! | `mirror-report` | `"warn"` | Report level of [`unit_tests_mirror!`](unit_tests_mirror!) mismatches, `"warn"`, `"error"` or `"none"`. |
+//! | `bench-folder`, `bench-cfg`, `bench-prefix` | `"tests/bench"`, `"bench"`, `"bench"` | Same for benchmarks. |
+//! | `doc-folder` | `"tests/doc"` | Documentation files folder. |
+//! | `root-{name}-folder`, `root-{name}-cfg`, `root-{name}-prefix` | None, `cfg`, `{name}` | Named root selected with `root = "{name}"`. |
+//! 
+//! The default `bench` cfg is custom, declare it with `unexpected_cfgs = { level = "warn", check-cfg = ['cfg(bench)'] }`
+//! under `[lints.rust]` of `Cargo.toml` to avoid `unexpected_cfgs` warnings.
+
+#![cfg_attr(feature = "nightly", feature(proc_macro_tracked_path, proc_macro_tracked_env))]
+
+use std::path::Path;
 
-use proc_macro::TokenStream;
-use crate::config::extract_unit_tests_parameters;
+use proc_macro::{TokenStream, TokenTree, Group, Delimiter, Literal};
+use crate::config::{extract_unit_tests_parameters, extract_doc_tests_parameters, extract_auto_tests_parameters, extract_mirror_tests_parameters, UnitTestParameters, UnitTestModule, UnitTestModuleSource, UnitTestMode, TestsKind, get_module_path, get_include_path};
+use crate::files::relative_path;
+use crate::errors::{to_compile_errors, to_compile_warnings};
+use crate::item::split_impl_methods;
+use crate::tracking::take_tracking_tokens;
 
 /// Configuration mod
 mod config;
@@ -29,44 +89,192 @@ mod config;
 /// Error enumeration mod
 mod errors;
 
+/// File system mod
+mod files;
+
+/// Item parsing mod
+mod item;
+
+/// Module names mod
+mod names;
+
+/// Cfg predicate mod
+mod cfg;
+
+/// Project settings mod
+mod settings;
+
+/// String literal mod
+mod literal;
+
+/// Cargo targets mod
+mod targets;
+
+/// Change tracking mod
+mod tracking;
+
 /// Link a unit tests module without an [item](https://doc.rust-lang.org/reference/items.html).
 /// 
 /// ### Syntax
 /// `unit__tests!("relative_path.rs" {, "module name"});`<br>
-/// *The element in `{}` are optional. The extension `.rs` is required.*
+/// `unit__tests!(path = "relative_path.rs" {, name = "module name"} {, mode = "module"} {, cfg(predicate)} {, fixtures = "relative_folder/"} {, confine = "strict"} {, root = "root name"} {, attrs(attribute, ...)});`<br>
+/// *The element in `{}` are optional. The extension `.rs` is required. Named parameters can be in any order.*
 /// 
 /// ### Path
 ///  By default, the macro will look in `{project_folder}/tests/unit/` for unit tests file.
 /// [This can be changed here](https://github.com/NickelAngeStudio/tests_bin/wiki/Customization)
 /// 
+/// Paths and names are string literals, raw or not, or built at build time with `concat!(...)` and `env!("VAR")`,
+/// like `concat!(env!("MY_TESTS"), "/add.rs")`.
+/// 
+/// Generated `#[path]` are relative to the invoking source file so builds are reproducible. Since `#[path]` in an inline
+/// module is relative to a folder named after it, macros invoked in inline modules or functions use full paths instead.
+/// 
+/// ### Confinement
+/// Linked paths must stay in the tests bin folder, so `..` and absolute paths leaving it are errors. The policy is given with
+/// `confine = "policy"` : `"strict"` (default) also rejects symbolic links leading outside, `"lexical"` follows them and
+/// `"none"` allows any path. The default policy can be changed with the `tests_bin-confine` key.
+/// 
+/// ### Named root
+/// `unit__tests!(root = "conformance", path = "parser.rs");`<br>
+/// Paths are relative to the folder of a root declared with the `tests_bin-root-{name}-folder` key instead of the tests bin folder.
+/// Each root has its own default cfg predicate and prefix of module names, given with `tests_bin-root-{name}-cfg` and
+/// `tests_bin-root-{name}-prefix`, the default cfg predicate and the root name if not set.
+/// 
+/// ### Glob pattern
+/// The path can be a glob pattern to link every matching file at once. `*` and `?` match within a folder or file name
+/// while `**` match any number of folders. Each module name is generated from the file relative path.
+/// 
+/// ### Module name
+/// Unless given, the module name is generated from the path, like `global_tests_rs` for `global_tests.rs`. When a generated
+/// name is already used in the same module, a short stable hash is appended, like `global_tests_rs_3f2a1c`.
+/// 
+/// ### Folder
+/// `unit__tests!(dir = "relative_folder" {, "module name"});`<br>
+/// Link every `.rs` file of a folder in a module tree mirroring its sub folders, so that `physics/collision/aabb.rs`
+/// becomes `physics::collision::aabb`. The root module is named after the folder unless a module name is given.
+/// 
 /// ### Example(s)
 /// 
 /// ```
 /// use tests_bin::unit__tests;
 /// 
 /// // Will link a module to `tests/unit/global_tests.rs`
-/// // with a module named `global_test_rs`.
+/// // with a module named `global_tests_rs`.
 /// unit__tests!("global_tests.rs");
 /// 
 /// // Will link a module to `tests/unit/target/target_tests.rs`
 /// // with a module named `my_target_tests`.
 /// unit__tests!("target/target_tests.rs", "my_target_tests");
+/// 
+/// // Will link a module for each `.rs` file in `tests/unit/operation/` and its sub folders.
+/// // `tests/unit/operation/multiply.rs` module is named `operation_multiply_rs`.
+/// unit__tests!("operation/**/*.rs");
+/// 
+/// // Will link a module tree of `tests/unit/target/` with a root module named `target`.
+/// // `tests/unit/target/target_tests.rs` module is `target::target_tests`.
+/// unit__tests!(dir = "target");
+/// 
+/// // Will link a module to `tests/unit/global_tests.rs`
+/// // with a module named `named_global_tests` allowing dead code.
+/// unit__tests!(path = "global_tests.rs", name = "named_global_tests", attrs(allow(dead_code)));
 /// ```
 #[allow(non_snake_case)]
 #[proc_macro]
 pub fn unit__tests(attr: TokenStream) -> TokenStream {
 
-     // Content tokens accumulator
-     let mut content = TokenStream::new();
+    link_tests(TestsKind::Unit, attr)
 
-     // 1. Extract parameters from attributes and parsed attributes
-     let parameters = extract_unit_tests_parameters(attr.clone(), attr.to_string().replace("\"", "").parse::<TokenStream>().unwrap());
- 
-     // 2. Add unit test module definition
-     content.extend(format!("#[cfg(test)]#[path = \"{}\"]mod {};", parameters.full_path, parameters.module_name).parse::<TokenStream>().unwrap());
- 
-     // 3. Return content tokenstream
-     content
+}
+
+/// Link every unit tests file of the tests bin folder in a module tree generated by `tests_bin_build`.
+/// 
+/// ### Syntax
+/// `unit_tests_auto!();`<br>
+/// *Requires `tests_bin_build` in `[build-dependencies]`.*
+/// 
+/// ### Build script
+/// `tests_bin_build::generate()` called from `build.rs` tells cargo to run it again when the tests bin folder changes, and
+/// writes its settings in `OUT_DIR`. The macro links a module tree mirroring the folder, with the root module named after
+/// the folder, so that `tests/unit/physics/aabb.rs` becomes `unit::physics::aabb`, and gated by `#[cfg(test)]`. New unit
+/// tests files are linked without invoking a macro for each of them.
+/// 
+/// ### Example(s)
+/// `build.rs`
+/// ```ignore
+/// fn main() {
+///     tests_bin_build::generate();
+/// }
+/// ```
+/// `lib.rs`
+/// ```ignore
+/// // Will link every `.rs` file of `tests/unit/` and its sub folders.
+/// tests_bin::unit_tests_auto!();
+/// ```
+#[proc_macro]
+pub fn unit_tests_auto(attr: TokenStream) -> TokenStream {
+
+    // 1. Extract module tree of tests bin folder
+    let mut content = match extract_auto_tests_parameters(attr) {
+
+        // 2. Add unit test modules definitions
+        Ok(parameters) => generate_unit_tests_modules(&parameters),
+
+        // 2. Or add all errors found
+        Err(errors) => to_compile_errors(errors),
+    };
+
+    // 3. Track configuration, builder settings and folders read
+    content.extend(take_tracking_tokens());
+
+    // 4. Return content tokenstream
+    content
+
+}
+
+/// Link the mirror unit tests file of every source file, so that `src/physics/aabb.rs` is tested by `tests/unit/physics/aabb.rs`.
+/// 
+/// ### Syntax
+/// `unit_tests_mirror!();`<br>
+/// *Invoked once, in `lib.rs` or `main.rs`.*
+/// 
+/// ### Module tree
+/// Mirror files are linked in a module tree named after the tests bin folder, so that `tests/unit/physics/aabb.rs` becomes
+/// `unit::physics::aabb`, gated by the default cfg predicate. A mirror file next to a folder of the same name, like
+/// `physics.rs` next to `physics/`, becomes `unit::physics::mod_` like `physics/mod.rs`. Files are linked as modules,
+/// so source items are imported with their path, like `use crate::physics::aabb::*;`.
+/// 
+/// ### Report
+/// Source files without mirror file and mirror files without source file are reported as warnings. They are reported as
+/// errors with the `tests_bin-mirror-report` key set to `"error"`, or not reported with `"none"`. Mirror files without
+/// source file aren't linked.
+/// 
+/// ### Example(s)
+/// `main.rs`
+/// ```ignore
+/// mod physics;
+/// 
+/// // Will link `tests/unit/physics.rs` in a module `unit::physics`.
+/// tests_bin::unit_tests_mirror!();
+/// ```
+#[proc_macro]
+pub fn unit_tests_mirror(attr: TokenStream) -> TokenStream {
+
+    // 1. Extract mirror files and mismatches
+    let mut content = match extract_mirror_tests_parameters(attr) {
+
+        // 2. Add unit test modules definitions
+        Ok(parameters) => generate_unit_tests_modules(&parameters),
+
+        // 2. Or add all errors found
+        Err(errors) => to_compile_errors(errors),
+    };
+
+    // 3. Track configuration and folders read
+    content.extend(take_tracking_tokens());
+
+    // 4. Return content tokenstream
+    content
 
 }
 
@@ -74,19 +282,68 @@ pub fn unit__tests(attr: TokenStream) -> TokenStream {
 /// 
 /// ### Syntax
 /// `#[unit_tests("relative_path.rs" {, "module name"})] item`<br>
-/// *The element in `{}` are optional. The extension `.rs` is required.*
+/// `#[unit_tests(path = "relative_path.rs" {, name = "module name"} {, mode = "module"} {, cfg(predicate)} {, fixtures = "relative_folder/"} {, confine = "strict"} {, root = "root name"} {, attrs(attribute, ...)})] item`<br>
+/// *The element in `{}` are optional. The extension `.rs` is required. Named parameters can be in any order.*
+/// 
+/// ### Attributes
+/// Attributes given with `attrs(...)` are added to the generated module, like `attrs(allow(dead_code))`.
+/// 
+/// ### Cfg predicate
+/// The generated module is gated by `#[cfg(test)]`. The predicate can be replaced with `cfg(predicate)` or 
+/// `cfg = "predicate"`, like `cfg(all(test, feature = "slow"))`. The default predicate can be changed
+/// with the `tests_bin-cfg` key. [See customization](https://github.com/NickelAngeStudio/tests_bin/wiki/Customization)
 /// 
 /// ### Path
 ///  By default, the macro will look in `{project_folder}/tests/unit/` for unit tests file.
 /// [This can be changed here](https://github.com/NickelAngeStudio/tests_bin/wiki/Customization)
 /// 
+/// Glob patterns and folders with `dir = "relative_folder"` are also accepted, [see `unit__tests!`](unit__tests!).
+/// 
+/// ### Include mode
+/// `#[unit_tests("relative_path.rs", mode = "include")] item`<br>
+/// Instead of linking the file with `#[path]`, the file is included in a module starting with `use super::*;`
+/// followed by the prelude of the `tests_bin-prelude` key, so that unit tests files only contain `#[test]` functions.
+/// Modes are `"module"` (default) and `"include"`. Also accepted by [`unit__tests!`](unit__tests!).
+/// 
+/// ### Fixtures
+/// `#[unit_tests("relative_path.rs", fixtures = "relative_folder/")] item`<br>
+/// The fixtures folder, relative to the tests bin folder or to the `fixtures` setting, is exposed to the module with the constant `FIXTURES : &str`
+/// relative to the project folder, and the helper `fixture(name : &str) -> PathBuf` resolving it from `CARGO_MANIFEST_DIR`
+/// at runtime, or else at build time. Outside of cargo, it falls back to the project folder found at build time. Also accepted by [`unit__tests!`](unit__tests!).
+/// 
+/// In module mode, the file is linked as the `tests` sub module of the module defining them, like `tests_fn_decode::tests`,
+/// so it keeps its inner attributes and reaches them with `use super::*;`.
+/// 
+/// ### Module name
+/// Unless given, the module name is generated from the item kind and name :
+/// - `fn`, `struct`, `enum`, `union`, `trait`, `type`, `const`, `static`, `mod` and `extern crate` like `tests_fn_add` or `tests_const_MAX`.
+/// - `macro_rules!` like `tests_macro_rules_max`.
+/// - `impl` blocks from trait and type like `tests_impl_Display_for_Point` or `tests_impl_Point`.
+/// - `extern` blocks from ABI like `tests_extern_C`.
+/// 
+/// When a generated name is already used in the same module, a short stable hash is appended, like `tests_fn_add_3f2a1c`.
+/// Before 2.0.0, names were generated from the item tokens, like `pub_fn_add_usize`.
+/// Other items like `use` declarations and macro invocations can't have a tests module.
+/// 
+/// ### Methods
+/// `#[unit_tests] impl Type { #[unit_test_file("relative_path.rs" {, "module name"})] fn method() {} }`<br>
+/// On an `impl` block, each method with the helper attribute `#[unit_test_file(...)]` is linked with its own tests module,
+/// placed next to the `impl` block and named after `Type::method` like `tests_Point_len`. The helper attribute accepts the
+/// same parameters as `#[unit_tests]`. The `impl` block itself only gets a tests module if `#[unit_tests]` has parameters.
+/// 
+/// ### Without path
+/// `#[unit_tests] item`<br>
+/// The path is derived from the item with the convention `{ident}.rs`, where `{ident}` is the item identifier.
+/// The convention can be changed with the `tests_bin-convention` key, for example `{kind}/{ident}.rs` where
+/// `{kind}` is the item keyword like `fn` or `struct`. [See customization](https://github.com/NickelAngeStudio/tests_bin/wiki/Customization)
+/// 
 /// ### Example(s)
 /// 
 /// ```
 /// use tests_bin::unit_tests;
 /// 
 /// // Will link a module to `tests/unit/add.rs`
-/// // with a module named `pub_fn_add_usize`.
+/// // with a module named `tests_fn_add`.
 /// #[unit_tests("add.rs")]
 /// pub fn add(left: usize, right: usize) -> usize {
 ///     left + right
@@ -98,23 +355,344 @@ pub fn unit__tests(attr: TokenStream) -> TokenStream {
 /// pub fn multiply(left: usize, right: usize) -> usize {
 ///     left * right
 /// }
+/// 
+/// // Will include `tests/unit/divide.rs` in a module named `tests_fn_divide`
+/// // starting with `use super::*;`.
+/// #[unit_tests("divide.rs", mode = "include")]
+/// pub fn divide(left: usize, right: usize) -> usize {
+///     left / right
+/// }
+/// 
+/// // Will link a module to `tests/unit/add.rs` with a module named 
+/// // `slow_add_tests` only compiled with `slow` feature tests.
+/// #[unit_tests("add.rs", "slow_add_tests", cfg(all(test, feature = "slow")))]
+/// pub fn add_slowly(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+/// 
+/// // Will link a module to `tests/unit/subtract.rs`
+/// // with a module named `tests_fn_subtract`.
+/// #[unit_tests]
+/// pub fn subtract(left: usize, right: usize) -> usize {
+///     left - right
+/// }
+/// 
+/// pub struct Calculator;
+/// 
+/// // Will link a module to `tests/unit/operation/multiply.rs`
+/// // with a module named `tests_Calculator_multiply`.
+/// #[unit_tests]
+/// impl Calculator {
+///     #[unit_test_file("operation/multiply.rs")]
+///     pub fn multiply(left: usize, right: usize) -> usize {
+///         left * right
+///     }
+/// }
 /// ```
 #[proc_macro_attribute]
 pub fn unit_tests(attr: TokenStream, item: TokenStream) -> TokenStream {
 
+    link_item_tests(TestsKind::Unit, attr, item)
+
+}
+
+/// Link a bench tests module without an [item](https://doc.rust-lang.org/reference/items.html).
+/// 
+/// ### Syntax
+/// Same as [`unit__tests!`](unit__tests!).
+/// 
+/// ### Path
+///  By default, the macro will look in `{project_folder}/tests/bench/` for bench tests file.
+/// This can be changed with the `tests_bin-bench-folder` key. [See customization](https://github.com/NickelAngeStudio/tests_bin/wiki/Customization)
+/// 
+/// ### Cfg predicate
+/// The generated module is gated by `#[cfg(bench)]`, enabled with `RUSTFLAGS="--cfg bench"`. The default predicate 
+/// can be changed with the `tests_bin-bench-cfg` key, like `feature = "bench"`, or with the `cfg` parameter.
+/// Declare `cfg(bench)` in `Cargo.toml` to avoid the `unexpected_cfgs` warning :
+/// ```toml
+/// [lints.rust]
+/// unexpected_cfgs = { level = "warn", check-cfg = ['cfg(bench)'] }
+/// ```
+/// 
+/// ### Example(s)
+/// 
+/// ```
+/// use tests_bin::bench__tests;
+/// 
+/// // Will link a module to `tests/bench/global_bench.rs`
+/// // with a module named `global_bench_rs`.
+/// bench__tests!("global_bench.rs");
+/// ```
+#[allow(non_snake_case)]
+#[proc_macro]
+pub fn bench__tests(attr: TokenStream) -> TokenStream {
+
+    link_tests(TestsKind::Bench, attr)
+
+}
+
+/// Link a bench tests module with an [item](https://doc.rust-lang.org/reference/items.html).
+/// 
+/// ### Syntax
+/// Same as [`#[unit_tests]`](macro@unit_tests), module name generated from item the same way with a `bench` prefix, like `bench_fn_add`.
+/// Methods of an `impl` block are linked with the helper attribute `#[bench_test_file(...)]`.
+/// 
+/// ### Path
+///  By default, the macro will look in `{project_folder}/tests/bench/` for bench tests file.
+/// This can be changed with the `tests_bin-bench-folder` key. [See customization](https://github.com/NickelAngeStudio/tests_bin/wiki/Customization)
+/// 
+/// ### Cfg predicate
+/// The generated module is gated by `#[cfg(bench)]`, enabled with `RUSTFLAGS="--cfg bench"`. The default predicate 
+/// can be changed with the `tests_bin-bench-cfg` key, like `feature = "bench"`, or with the `cfg` parameter.
+/// Declare `cfg(bench)` in `Cargo.toml` to avoid the `unexpected_cfgs` warning :
+/// ```toml
+/// [lints.rust]
+/// unexpected_cfgs = { level = "warn", check-cfg = ['cfg(bench)'] }
+/// ```
+/// 
+/// ### Example(s)
+/// 
+/// ```
+/// use tests_bin::bench_tests;
+/// 
+/// // Will link a module to `tests/bench/add.rs`
+/// // with a module named `bench_fn_add`.
+/// #[bench_tests("add.rs")]
+/// pub fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn bench_tests(attr: TokenStream, item: TokenStream) -> TokenStream {
+
+    link_item_tests(TestsKind::Bench, attr, item)
+
+}
+
+/// Attach a documentation file to an [item](https://doc.rust-lang.org/reference/items.html).
+/// 
+/// ### Syntax
+/// `#[doc_tests("relative_path.md")] item`<br>
+/// *The extension `.md` or `.rs` is required.*
+/// 
+/// ### Path
+///  By default, the macro will look in `{project_folder}/tests/doc/` for documentation file.
+/// This can be changed with the `tests_bin-doc-folder` key. [See customization](https://github.com/NickelAngeStudio/tests_bin/wiki/Customization)
+/// 
+/// ### Documentation
+/// A `.md` file is added as is to the item documentation while a `.rs` file is wrapped in a code block. Its examples
+/// are rendered by `rustdoc` and run by `cargo test --doc` like any other documentation.
+/// 
+/// ### Example(s)
+/// 
+/// ```
+/// use tests_bin::doc_tests;
+/// 
+/// // Will add `tests/doc/add.md` to `add` documentation.
+/// #[doc_tests("add.md")]
+/// pub fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+/// 
+/// // Will add `tests/doc/multiply.rs` in a code block to `multiply` documentation.
+/// #[doc_tests("multiply.rs")]
+/// pub fn multiply(left: usize, right: usize) -> usize {
+///     left * right
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn doc_tests(attr: TokenStream, item: TokenStream) -> TokenStream {
+
+    // 1. Extract doc tests path from attributes
+    let documentation = match extract_doc_tests_parameters(attr) {
+
+        // 2. Add documentation attribute, in a code block for `.rs` file.
+        Ok(parameters) => match parameters.code {
+            true => format!("#[doc = concat!(\"```\\n\", include_str!({}), \"\\n```\")]", Literal::string(&parameters.link_path)).parse::<TokenStream>().unwrap(),
+            false => format!("#[doc = include_str!({})]", Literal::string(&parameters.link_path)).parse::<TokenStream>().unwrap(),
+        },
+
+        // 2. Or add all errors found
+        Err(errors) => to_compile_errors(errors),
+    };
+
+    // 3. Drop tracking items, since `const _` can't be emitted next to methods of `impl` and `trait` blocks. Documentation
+    //    file is already tracked by `include_str!`.
+    let _ = take_tracking_tokens();
+    let mut content = documentation;
+
+    // 4. Add items to content, even on error to prevent cascading errors.
+    content.extend(item);
+
+    // 5. Return content tokenstream
+    content
+
+}
+
+/// Link tests modules of kind without an item.
+fn link_tests(kind: TestsKind, attr: TokenStream) -> TokenStream {
+
+     // Content tokens accumulator
+     let mut content = TokenStream::new();
+
+     // 1. Extract parameters from attributes. Module name is generated from path.
+     match extract_unit_tests_parameters(kind, attr, None, None) {
+ 
+          // 2. Add unit test module definition
+          Ok(parameters) => content.extend(generate_unit_tests_modules(&parameters)),
+
+          // 2. Or add all errors found
+          Err(errors) => content.extend(to_compile_errors(errors)),
+     }
+
+     // 3. Track configuration and folders read
+     content.extend(take_tracking_tokens());
+ 
+     // 4. Return content tokenstream
+     content
+
+}
+
+/// Link tests modules of kind with an item.
+/// 
+/// Methods of an `impl` block with the helper attribute of kind are linked with their own parameters, and the `impl` block
+/// only gets a module if parameters are given.
+fn link_item_tests(kind: TestsKind, attr: TokenStream, item: TokenStream) -> TokenStream {
+
     // Content tokens accumulator
     let mut content = TokenStream::new();
 
-    // 1. Extract parameters from attributes and items
-    let parameters = extract_unit_tests_parameters(attr.clone(), item.clone());
+    // 1. Remove helper attributes of impl block methods
+    let (item, methods) = match split_impl_methods(item.clone(), kind.method_attribute()) {
+        Some((owner, item, methods)) => (item, Some((owner, methods))),
+        None => (item, None),
+    };
+
+    // 2. Extract parameters from attributes and items
+    if methods.is_none() || !attr.is_empty() {
+        match extract_unit_tests_parameters(kind, attr, Some(item.clone()), None) {
+
+            // 3. Add unit test module definition
+            Ok(parameters) => content.extend(generate_unit_tests_modules(&parameters)),
 
-    // 2. Add unit test module definition
-    content.extend(format!("#[cfg(test)]#[path = \"{}\"]mod {};", parameters.full_path, parameters.module_name).parse::<TokenStream>().unwrap());
+            // 3. Or add all errors found
+            Err(errors) => content.extend(to_compile_errors(errors)),
+        }
+    }
+
+    // 4. Add methods unit test modules definitions next to impl block, named after impl type.
+    if let Some((owner, methods)) = methods {
+        for method in methods {
+            for attr in method.parameters {
+                match extract_unit_tests_parameters(kind, attr, Some(method.item.clone()), Some(&owner)) {
+                    Ok(parameters) => content.extend(generate_unit_tests_modules(&parameters)),
+                    Err(errors) => content.extend(to_compile_errors(errors)),
+                }
+            }
+        }
+    }
     
-    // 3. Add items to content
+    // 5. Track configuration and folders read
+    content.extend(take_tracking_tokens());
+
+    // 6. Add items to content, even on error to prevent cascading errors.
     content.extend(item);
 
-    // 4. Return content tokenstream
+    // 7. Return content tokenstream
     content
 
-}
\ No newline at end of file
+}
+
+/// Generate unit tests modules definitions from parameters.
+fn generate_unit_tests_modules(parameters: &UnitTestParameters) -> TokenStream {
+
+    let mut content = to_compile_warnings(&parameters.warnings);
+
+    for module in parameters.modules.iter() {
+        content.extend(format!("#[cfg({})] #[allow(non_snake_case)]", parameters.cfg).parse::<TokenStream>().unwrap());
+        content.extend(parameters.attributes.clone());
+        content.extend(generate_unit_tests_module(module, parameters, None));
+    }
+
+    content
+
+}
+
+/// Generate a unit tests module definition, with its sub modules if it is a folder.
+/// 
+/// In include mode, modules import their parent items and the prelude. Files with fixtures are
+/// included in a module defining `FIXTURES` and `fixture()`, or linked as its `tests` sub module in module mode.
+/// 
+/// `#[path]` of sub modules of a folder are relative to their parent folder, given by `#[path]` of the folder module.
+/// Other paths are relative to the invoking source file and its enclosing inline modules, see [`get_module_path`] and [`get_include_path`].
+fn generate_unit_tests_module(module: &UnitTestModule, parameters: &UnitTestParameters, parent: Option<&Path>) -> TokenStream {
+
+    // Imports of include mode modules, prelude tokens are spliced since it may end with a comment
+    let mut imports = TokenStream::new();
+    if parameters.mode == UnitTestMode::Include {
+        imports.extend("#[allow(unused_imports)] use super::*;".parse::<TokenStream>().unwrap());
+        imports.extend(parameters.prelude.clone());
+    }
+
+    // Fixtures folder constant and helper
+    // Project root found at build time is only embedded outside of cargo
+    let fixtures = match &parameters.fixtures {
+        Some(fixtures) => format!("#[allow(dead_code)] const FIXTURES : &str = {}; 
+            #[allow(dead_code)] fn fixture(name : &str) -> ::std::path::PathBuf {{ 
+                ::std::env::var_os(\"CARGO_MANIFEST_DIR\").map(::std::path::PathBuf::from).unwrap_or_else(|| ::std::path::PathBuf::from({})).join(FIXTURES).join(name) }}", 
+                Literal::string(fixtures), match &parameters.root {
+                    Some(root) => Literal::string(root).to_string(),
+                    None => String::from("env!(\"CARGO_MANIFEST_DIR\")"),
+                }).parse::<TokenStream>().unwrap(),
+        None => TokenStream::new(),
+    };
+
+    // Path of `#[path]` attribute
+    let module_path = |path: &Path| match parent {
+        Some(folder) => relative_path(folder, path).unwrap_or_else(|| path.to_string_lossy().to_string()),
+        None => get_module_path(path),
+    };
+
+    match &module.source {
+        UnitTestModuleSource::File(path) => match (&parameters.mode, &parameters.fixtures) {
+            (UnitTestMode::Module, None) => format!("#[path = {}] mod {};", Literal::string(&module_path(path)), module.module_name).parse::<TokenStream>().unwrap(),
+
+            // File keeps its inner attributes and `mod` declarations in a `tests` module, next to fixtures of its parent
+            (UnitTestMode::Module, Some(_)) => {
+                let folder = path.parent().unwrap_or(path);
+                let file = path.file_name().map(|file| file.to_string_lossy().to_string()).unwrap_or_default();
+                let mut content = "#[allow(unused_imports)] use super::*;".parse::<TokenStream>().unwrap();
+                content.extend(fixtures);
+                content.extend(format!("#[path = {}] mod tests;", Literal::string(&file)).parse::<TokenStream>().unwrap());
+                let mut wrapper = format!("#[path = {}]", Literal::string(&module_path(folder))).parse::<TokenStream>().unwrap();
+                wrapper.extend(generate_inline_module(&module.module_name, content));
+                wrapper
+            },
+            _ => {
+                let mut content = imports;
+                content.extend(fixtures);
+                content.extend(format!("include!({});", Literal::string(&get_include_path(path))).parse::<TokenStream>().unwrap());
+                generate_inline_module(&module.module_name, content)
+            },
+        },
+        UnitTestModuleSource::Folder(path, children) => {
+            let mut content = imports;
+            for child in children {
+                content.extend(generate_unit_tests_module(child, parameters, Some(path)));
+            }
+            let mut folder = format!("#[path = {}]", Literal::string(&module_path(path))).parse::<TokenStream>().unwrap();
+            folder.extend(generate_inline_module(&module.module_name, content));
+            folder
+        },
+    }
+
+}
+
+/// Generate `mod name { content }` from content tokens.
+fn generate_inline_module(name: &str, content: TokenStream) -> TokenStream {
+
+    let mut module = format!("mod {}", name).parse::<TokenStream>().unwrap();
+    module.extend([TokenTree::Group(Group::new(Delimiter::Brace, content))]);
+    module
+
+}
@@ -0,0 +1,185 @@
+use proc_macro::{TokenStream, TokenTree, Literal, Ident, Group};
+
+use crate::errors::{TestsBinErrors, TestsBinError};
+use crate::tracking::track_env;
+
+// Contants
+const CONCAT_MACRO : &str = "concat";                               // Macro concatenating string expressions
+const ENV_MACRO : &str = "env";                                     // Macro reading an env variable at build time
+const ARGUMENTS_SEPARATOR : char = ',';                             // Separator of macro arguments
+const RAW_PREFIX : char = 'r';                                      // Prefix of raw string literals
+const RAW_HASH : char = '#';                                        // Delimiter of raw string literals
+
+/// Decode a string literal representation, raw or not, without `""` and escapes.
+///
+/// Returns `None` if literal isn't a string, like numbers, characters, byte strings or suffixed strings.
+pub(crate) fn decode_string_literal(literal: &str) -> Option<String> {
+
+    // Raw strings like `r"..."` or `r#"..."#` have no escapes
+    if let Some(raw) = literal.strip_prefix(RAW_PREFIX) {
+        let content = raw.trim_start_matches(RAW_HASH);
+        let hashes = &raw[..raw.len() - content.len()];
+        return content.strip_prefix('"')?.strip_suffix(hashes)?.strip_suffix('"').map(String::from);
+    }
+
+    let content = literal.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut decoded = String::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => decoded.push('\n'),
+            Some('r') => decoded.push('\r'),
+            Some('t') => decoded.push('\t'),
+            Some('0') => decoded.push('\0'),
+            Some('x') => {
+                let code : String = chars.by_ref().take(2).collect();
+                if let Some(c) = u8::from_str_radix(&code, 16).ok().map(char::from) {
+                    decoded.push(c);
+                }
+            },
+            Some('u') => {
+                let code : String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+                if let Some(c) = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    decoded.push(c);
+                }
+            },
+            // Line continuation skips following whitespaces
+            Some('\n') => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            Some(c) => decoded.push(c),     // `\\`, `\"` and `\'`
+            None => {},
+        }
+    }
+
+    Some(decoded)
+
+}
+
+/// Replace string macros `concat!(...)` and `env!("VAR")` of stream by the string literal they expand to.
+///
+/// Only top level macros are expanded, so groups like `attrs(...)` are kept as is. Macros that can't be
+/// expanded are reported in errors and removed.
+pub(crate) fn expand_string_macros(stream: TokenStream, errors: &mut Vec<TestsBinError>) -> TokenStream {
+
+    let mut content : Vec<TokenTree> = Vec::new();
+    let mut tokens = stream.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            // Macro invocation `name!(...)`
+            TokenTree::Ident(ident) if matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '!') => {
+                tokens.next();  // Skip `!`
+                match tokens.next() {
+                    Some(TokenTree::Group(arguments)) => {
+                        if let Some(value) = evaluate_string_macro(&ident, &arguments, errors) {
+                            let mut literal = Literal::string(&value);
+                            literal.set_span(ident.span());
+                            content.push(TokenTree::Literal(literal));
+                        }
+                    },
+                    Some(token) => errors.push(TestsBinError::new(token.span(), TestsBinErrors::IncorrectParameters)),
+                    None => errors.push(TestsBinError::new(ident.span(), TestsBinErrors::IncorrectParameters)),
+                }
+            },
+            _ => content.push(token),
+        }
+    }
+
+    content.into_iter().collect()
+
+}
+
+/// Evaluate the string value of `concat!(...)` or `env!("VAR")`.
+///
+/// `concat!` accepts string literals and string macros separated by `,`.
+///
+/// Error(s)
+/// Returns `None` and add errors if macro isn't supported, arguments aren't strings or env variable isn't set.
+fn evaluate_string_macro(ident: &Ident, arguments: &Group, errors: &mut Vec<TestsBinError>) -> Option<String> {
+
+    let name = ident.to_string();
+    if name != CONCAT_MACRO && name != ENV_MACRO {
+        errors.push(TestsBinError::new(ident.span(), TestsBinErrors::UnsupportedMacro(name)));
+        return None;
+    }
+
+    let count = errors.len();
+    let mut values : Vec<String> = Vec::new();
+    let mut separator : bool = true;            // No separator needed before first argument
+
+    // Nested macros are expanded first
+    for token in expand_string_macros(arguments.stream(), errors) {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ARGUMENTS_SEPARATOR && !separator => separator = true,
+            TokenTree::Literal(literal) if separator => {
+                match decode_string_literal(literal.to_string().as_str()) {
+                    Some(value) => values.push(value),
+                    None => errors.push(TestsBinError::new(literal.span(), TestsBinErrors::IncorrectLiteral(literal.to_string()))),
+                }
+                separator = false;
+            },
+            _ => errors.push(TestsBinError::new(token.span(), TestsBinErrors::IncorrectParameters)),
+        }
+    }
+
+    if errors.len() > count {
+        return None;
+    }
+
+    match name.as_str() {
+        ENV_MACRO => match values.as_slice() {
+            [name] => {
+                track_env(name);
+                match std::env::var(name) {
+                    Ok(value) => Some(value),
+                    Err(_) => {
+                        errors.push(TestsBinError::new(ident.span(), TestsBinErrors::EnvVarNotSet(name.clone())));
+                        None
+                    },
+                }
+            },
+            _ => {
+                errors.push(TestsBinError::new(arguments.span(), TestsBinErrors::IncorrectParameters));
+                None
+            },
+        },
+        _ => Some(values.concat()),
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::literal::decode_string_literal;
+
+    /// Test string literals decoding with escapes
+    #[test]
+    fn decode_string_literal_escapes() {
+
+        assert_eq!(decode_string_literal(r#""ponyo/ham.rs""#).unwrap(), "ponyo/ham.rs", "Quotes should be removed!");
+        assert_eq!(decode_string_literal(r#""all(test, feature = \"slow\")""#).unwrap(), r#"all(test, feature = "slow")"#, "Escaped quotes should be kept!");
+        assert_eq!(decode_string_literal(r#""a\\b\tc""#).unwrap(), "a\\b\tc", "Escaped backslash and tab should be decoded!");
+        assert_eq!(decode_string_literal(r#""\x41\u{1F980}""#).unwrap(), "A\u{1F980}", "Ascii and unicode escapes should be decoded!");
+
+    }
+
+    /// Test raw string literals and rejection of other literals
+    #[test]
+    fn decode_string_literal_raw() {
+
+        assert_eq!(decode_string_literal(r#"r"ponyo\ham.rs""#).unwrap(), r"ponyo\ham.rs", "Raw string should keep backslashes!");
+        assert_eq!(decode_string_literal(r###"r##"say "ham".rs"##"###).unwrap(), r#"say "ham".rs"#, "Raw string should keep quotes!");
+        assert!(decode_string_literal("42").is_none(), "Numbers should be rejected!");
+        assert!(decode_string_literal("'a'").is_none(), "Characters should be rejected!");
+        assert!(decode_string_literal(r#"b"ham.rs""#).is_none(), "Byte strings should be rejected!");
+        assert!(decode_string_literal(r#""ham.rs"suffix"#).is_none(), "Suffixed strings should be rejected!");
+
+    }
+
+}
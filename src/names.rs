@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use proc_macro::{TokenStream, Span};
+
+use crate::files::BraceScope;
+use crate::item::parse_item_signature;
+use crate::settings::get_call_site_scopes;
+
+// Contants
+const ILLEGAL_CHARACTER_REPLACE : char = '_';                       // Illegal character will be replaceby this
+const UNKNOWN_ITEM_KIND : &str = "item";                            // Kind used in module name of unrecognized items
+const HASH_OFFSET : u32 = 0x811c9dc5;                               // FNV-1a 32 bits offset basis
+const HASH_PRIME : u32 = 0x01000193;                                // FNV-1a 32 bits prime
+const HASH_MASK : u32 = 0xffffff;                                   // Hash suffix keeps 24 bits, 6 hexadecimal digits
+const KEYWORDS : [&str; 51] = ["as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop",
+    "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "typeof", "unsized", "virtual", "yield", "try"];  // Keywords that can't be module names.
+
+/// Line and column of a macro call site, with the seed of the registered name.
+///
+/// Seed tells apart names registered by the same call site, like two `#[unit_test_file]` of a method.
+type CallSite = (usize, usize, String);
+
+/// Generated module names of each source file and scope, with the call sites that registered them in order.
+///
+/// Macros of a crate are expanded by the same process, so it is shared by every invocation. Long-lived proc macro
+/// servers, like rust-analyzer's, keep call sites of edited lines, so names shown in the editor may get a hash suffix
+/// until the server restarts. Builds with cargo aren't affected since each build expands macros in a new process.
+static MODULE_NAMES : Mutex<BTreeMap<(String, String, String), Vec<CallSite>>> = Mutex::new(BTreeMap::new());
+
+/// Generate tests module name from item kind and name, like `tests_fn_add` or `tests_impl_Display_for_Point`.
+///
+/// Associated items of an `impl` block are named after their owner type instead, like `tests_Point_len` for `Point::len`.
+///
+/// Cannot be unit tested because of TokenStream.
+#[inline(always)]
+pub(crate) fn generate_test_mod_name(prefix: &str, owner: Option<&str>, item: TokenStream) -> String {
+
+    match parse_item_signature(item) {
+        Some(signature) => match (owner, signature.name) {
+            (Some(owner), Some(name)) => format!("{}_{}_{}", prefix, generate_ident(owner), generate_ident(&name)),
+            (_, Some(name)) => format!("{}_{}_{}", prefix, signature.kind, generate_ident(&name)),
+            (_, None) => format!("{}_{}", prefix, signature.kind),
+        },
+        None => format!("{}_{}", prefix, UNKNOWN_ITEM_KIND),
+    }
+
+}
+
+/// Register a generated module name at macro call site and returns it, with a short hash suffix
+/// if another call site of the same file and scope, or the same call site with another seed, already generated it.
+///
+/// Scope is the path of braces enclosing the call site, so sibling inline modules like `a` and `b` can both have `tests_fn_add`.
+///
+/// Suffix is derived from name, seed and collision index, so it's stable between builds. Expanding
+/// the same call site again returns the same name.
+pub(crate) fn register_module_name(name: String, seed: &str) -> String {
+
+    let call_site = Span::call_site();
+    let location = (call_site.line(), call_site.column(), seed.to_string());
+
+    // A poisoned registry is still usable since it's only appended to.
+    let mut names = MODULE_NAMES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let locations = names.entry((call_site.file(), get_call_site_scope(), name.clone())).or_default();
+
+    let index = match locations.iter().position(|registered| *registered == location) {
+        Some(index) => index,
+        None => {
+            locations.push(location);
+            locations.len() - 1
+        },
+    };
+
+    match index {
+        0 => name,
+        index => format!("{}_{:06x}", name, short_hash(format!("{}{}{}", name, seed, index).as_str())),
+    }
+
+}
+
+/// Get path of braces enclosing the call site, like `physics::shapes` for inline modules. Other blocks are
+/// named after their position, like `physics::{1024}`.
+fn get_call_site_scope() -> String {
+
+    get_call_site_scopes().unwrap_or_default().iter().map(|scope| match scope {
+        BraceScope::Module(name) | BraceScope::PathModule(name) => name.clone(),
+        BraceScope::Block(position) => format!("{{{}}}", position),
+    }).collect::<Vec<String>>().join("::")
+
+}
+
+/// Hash value into 24 bits with FNV-1a, which is stable across builds and platforms.
+pub(crate) fn short_hash(value: &str) -> u32 {
+
+    let hash = value.bytes().fold(HASH_OFFSET, |hash, byte| (hash ^ byte as u32).wrapping_mul(HASH_PRIME));
+    hash & HASH_MASK
+
+}
+
+/// Generate a module identifier from a file or folder name.
+///
+/// Will replace illegal characters with _, and add _ to keywords and names starting with a digit.
+#[inline(always)]
+pub(crate) fn generate_ident(name: &str) -> String {
+
+    let mut ident : String = name.chars().map(|c| if c.is_alphanumeric() || c == '_' { c } else { ILLEGAL_CHARACTER_REPLACE }).collect();
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_numeric()) {
+        ident.insert(0, ILLEGAL_CHARACTER_REPLACE);
+    }
+
+    if ident == "_" || KEYWORDS.contains(&ident.as_str()) {
+        ident.push(ILLEGAL_CHARACTER_REPLACE);
+    }
+
+    ident
+
+}
+
+/// Returns true if name is a valid rust identifier.
+#[inline(always)]
+pub(crate) fn is_valid_identifier(name: &str) -> bool {
+
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) => (first.is_alphabetic() || first == '_') && name != "_" && !KEYWORDS.contains(&name) && chars.all(|c| c.is_alphanumeric() || c == '_'),
+        None => false,
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::names::{generate_ident, is_valid_identifier, short_hash};
+
+    /// Test identifiers generated from file and folder names
+    #[test]
+    fn generate_ident_names() {
+
+        assert_eq!(generate_ident("ponyo/ham.rs"), "ponyo_ham_rs", "Illegal characters should be replaced!");
+        assert_eq!(generate_ident("01_add"), "_01_add", "Leading digit should be prefixed!");
+        assert_eq!(generate_ident("fn"), "fn_", "Keywords should be suffixed!");
+        assert!(is_valid_identifier("Display_for_Point"), "`Display_for_Point` should be valid!");
+        assert!(!is_valid_identifier("mod"), "Keywords should not be valid!");
+
+    }
+
+    /// Test hash suffix is stable and short
+    #[test]
+    fn short_hash_stable() {
+
+        assert_eq!(short_hash("tests_fn_add"), short_hash("tests_fn_add"), "Same value should have same hash!");
+        assert_ne!(short_hash("tests_fn_add1"), short_hash("tests_fn_add2"), "Different values should have different hash!");
+        assert!(short_hash("tests_fn_add") <= 0xffffff, "Hash should fit 6 hexadecimal digits!");
+
+    }
+
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use proc_macro::Span;
+use tests_bin_settings::{ProjectSettings, SETTINGS_FILE, CARGO_FILE};
+
+use crate::errors::TestsBinErrors;
+use crate::files::{relative_path, get_implied_folder, BraceScope, SourceBraces};
+use crate::targets::get_target_name;
+use crate::tracking::{track_env, track_file};
+
+// Contants
+const CARGO_MANIFEST_DIR : &str = "CARGO_MANIFEST_DIR";             // Cargo manifest directory key
+const CARGO_CRATE_NAME : &str = "CARGO_CRATE_NAME";                 // Cargo crate name key
+const TESTS_BIN_ROOT : &str = "TESTS_BIN_ROOT";                     // Project root key, used when built outside of cargo
+const SOURCE_FOLDER : &str = "src";                                 // Source folder, its parent is the project root
+const ROOT_MARKERS : [&str; 2] = [SETTINGS_FILE, CARGO_FILE];      // Files marking the project root
+
+/// Settings of each project folder, read once since settings are fetched many times by each invocation.
+/// 
+/// Settings are read again when one of their files changes, since long-lived proc macro servers like rust-analyzer's
+/// keep the process between edits.
+static PROJECT_SETTINGS : Mutex<BTreeMap<PathBuf, ProjectSettings>> = Mutex::new(BTreeMap::new());
+
+/// Braces of each invoking source file with its modification time, lexed once since every call site of a file looks up its scopes.
+/// 
+/// Braces are lexed again when the file changes, like settings.
+static SOURCE_BRACES : Mutex<BTreeMap<PathBuf, (Option<SystemTime>, SourceBraces)>> = Mutex::new(BTreeMap::new());
+
+/// Get a setting value from, in order of precedence :
+/// 1. Env key like `tests_bin-folder`, usually set in `.cargo/config.toml` `[env]`.
+/// 2. Key without `tests_bin-` prefix in `tests_bin.toml` next to `Cargo.toml`, like `folder = "tests/unit"`.
+/// 3. Key without `tests_bin-` prefix in `[package.metadata.tests_bin]` of `Cargo.toml`.
+/// 4. Key without `tests_bin-` prefix in `[workspace.metadata.tests_bin]` of workspace root `Cargo.toml`.
+///
+/// Placeholders `{workspace}`, `{crate}` and `{target}` of value are replaced by workspace root folder, relative to
+/// `Cargo.toml` folder, package name and name of target being compiled.
+/// 
+/// Returns `None` if setting isn't set, so the default value is used.
+pub(crate) fn get_setting(env_key: &str) -> Option<String> {
+
+    // Env keys are still read when project root isn't found
+    let settings = get_project_root().map(|manifest_dir| get_project_settings(Path::new(&manifest_dir))).unwrap_or_default();
+
+    track_env(env_key);
+    settings.get(env_key, Some(get_target_name().unwrap_or_default().as_str()))
+
+}
+
+/// Get the error of settings files using a syntax that can't be read, like inline tables or dotted keys.
+/// 
+/// Returns `None` if settings files can be read or project root isn't found.
+pub(crate) fn get_settings_error() -> Option<TestsBinErrors> {
+
+    let manifest_dir = get_project_root()?;
+    let settings = get_project_settings(Path::new(&manifest_dir));
+    let (file, line) = settings.error()?;
+
+    Some(TestsBinErrors::UnsupportedSettings { file: relative_path(Path::new(&manifest_dir), file).unwrap_or(file.display().to_string()), line: line.to_string() })
+
+}
+
+/// Get settings of project folder, read again if one of their files changed. Files read are tracked.
+fn get_project_settings(manifest_dir: &Path) -> ProjectSettings {
+
+    // A poisoned cache is still usable since settings are only replaced.
+    let mut cache = PROJECT_SETTINGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let settings = match cache.get(manifest_dir) {
+        Some(settings) if settings.is_current() => settings.clone(),
+        _ => {
+            let settings = ProjectSettings::read(manifest_dir);
+            cache.insert(manifest_dir.to_path_buf(), settings.clone());
+            settings
+        },
+    };
+
+    for file in settings.files() {
+        track_file(file);
+    }
+
+    settings
+
+}
+
+/// Get the project root folder, which tests bin folders and settings files are relative to, from in order :
+/// 1. `CARGO_MANIFEST_DIR` set by cargo.
+/// 2. `TESTS_BIN_ROOT` env var, for builds outside of cargo like Bazel, Buck or plain `rustc`.
+/// 3. Nearest folder of the invoking source file that is the parent of a `src` folder containing it, or
+///    that contains a root marker file `tests_bin.toml` or `Cargo.toml`.
+/// 
+/// Returns `None` if project root can't be found.
+pub(crate) fn get_project_root() -> Option<String> {
+
+    if let Ok(manifest_dir) = std::env::var(CARGO_MANIFEST_DIR) {
+        return Some(manifest_dir);
+    }
+
+    // Relative root is relative to the compiler working directory
+    track_env(TESTS_BIN_ROOT);
+    if let Ok(root) = std::env::var(TESTS_BIN_ROOT) {
+        return Some(std::env::current_dir().map(|dir| dir.join(&root)).unwrap_or(PathBuf::from(root)).to_string_lossy().to_string());
+    }
+
+    find_project_root(&get_invoking_file()?).map(|root| root.to_string_lossy().to_string())
+
+}
+
+/// Get the project root when built outside of cargo, for paths that can't be resolved from `CARGO_MANIFEST_DIR` at runtime.
+/// 
+/// Returns `None` when built by cargo, so that absolute paths aren't embedded in expanded code.
+pub(crate) fn get_project_root_outside_cargo() -> Option<String> {
+
+    match std::env::var_os(CARGO_MANIFEST_DIR) {
+        Some(_) => None,
+        None => get_project_root(),
+    }
+
+}
+
+/// Find the nearest folder of source file that is the parent of a `src` folder or contains a root marker file.
+pub(crate) fn find_project_root(file: &Path) -> Option<PathBuf> {
+
+    file.ancestors().skip(1)
+        .find_map(|folder| match folder.file_name() {
+            Some(name) if name == SOURCE_FOLDER => folder.parent(),
+            _ => ROOT_MARKERS.iter().any(|marker| folder.join(marker).is_file()).then_some(folder),
+        })
+        .map(Path::to_path_buf)
+
+}
+
+/// Get the absolute path of the source file invoking the macro.
+/// 
+/// Returns `None` if the source file isn't a local file.
+pub(crate) fn get_invoking_file() -> Option<PathBuf> {
+
+    Span::call_site().local_file().and_then(|file| std::env::current_dir().ok().map(|dir| dir.join(file)))
+
+}
+
+/// Get braces enclosing the macro call site in its source file, from outermost to innermost.
+/// 
+/// Returns `None` if the source file can't be read.
+pub(crate) fn get_call_site_scopes() -> Option<Vec<BraceScope>> {
+
+    let call_site = Span::call_site();
+    let file = get_invoking_file()?;
+    let modified = std::fs::metadata(&file).and_then(|metadata| metadata.modified()).ok();
+
+    // A poisoned cache is still usable since entries are replaced whole.
+    let mut sources = SOURCE_BRACES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !matches!(sources.get(&file), Some((lexed, _)) if modified.is_some() && *lexed == modified) {
+        let content = std::fs::read_to_string(&file).ok()?;
+        sources.insert(file.clone(), (modified, SourceBraces::new(&content)));
+    }
+
+    sources.get(&file).and_then(|(_, braces)| braces.get_scopes(call_site.line(), call_site.column()))
+
+}
+
+/// Get folder `#[path]` attributes are relative to at macro call site, see [`get_implied_folder`].
+pub(crate) fn get_module_folder() -> Option<PathBuf> {
+
+    let file = get_invoking_file()?;
+    let crate_name = std::env::var(CARGO_CRATE_NAME).ok();
+
+    get_implied_folder(&file, &get_call_site_scopes()?, crate_name.as_deref())
+
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::settings::find_project_root;
+
+    /// Test project root is the parent of `src` or the folder of a root marker
+    #[test]
+    fn find_project_root_folders() {
+
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        assert_eq!(find_project_root(&manifest_dir.join("src/lib.rs")).unwrap(), manifest_dir, "Parent of `src` should be the root!");
+        assert_eq!(find_project_root(&manifest_dir.join("src/ponyo/ham.rs")).unwrap(), manifest_dir, "Parent of `src` should be the root of nested files!");
+        assert_eq!(find_project_root(&manifest_dir.join("tests/tests.rs")).unwrap(), manifest_dir, "Folder of `Cargo.toml` should be the root!");
+
+    }
+
+}
@@ -0,0 +1,15 @@
+// Contants
+const CARGO_BIN_NAME : &str = "CARGO_BIN_NAME";                     // Name of binary target being compiled
+const CARGO_CRATE_NAME : &str = "CARGO_CRATE_NAME";                 // Name of crate being compiled
+
+/// Get the name of target being compiled, the binary name or the crate name for other targets.
+/// 
+/// Each target is expanded by its own compiler process, possibly in parallel, so macros can't know which files other
+/// targets link. Files linked from two targets are found by `tests_bin_build` from the sources of every target.
+/// 
+/// Returns `None` outside of cargo.
+pub(crate) fn get_target_name() -> Option<String> {
+
+    std::env::var(CARGO_BIN_NAME).or_else(|_| std::env::var(CARGO_CRATE_NAME)).ok()
+
+}
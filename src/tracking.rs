@@ -0,0 +1,82 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use proc_macro::{TokenStream, Literal};
+
+/// Env variables read since tracking tokens were last taken.
+///
+/// Macros of a crate are expanded one at a time by the same process, so each invocation takes its own inputs.
+static TRACKED_ENV : Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Files read since tracking tokens were last taken.
+static TRACKED_FILES : Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// Track an env variable read by macros, so that changing it expands them again.
+pub(crate) fn track_env(key: &str) {
+
+    #[cfg(feature = "nightly")]
+    if proc_macro::is_available() {
+        let _ = proc_macro::tracked::env_var(key);
+        return;
+    }
+
+    TRACKED_ENV.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(key.to_string());
+
+}
+
+/// Track a file read by macros, so that changing or removing it expands them again. Missing files aren't tracked.
+pub(crate) fn track_file(file: &Path) {
+
+    if !file.is_file() {
+        return;
+    }
+
+    #[cfg(feature = "nightly")]
+    if proc_macro::is_available() {
+        proc_macro::tracked::path(file);
+        return;
+    }
+
+    TRACKED_FILES.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(file.to_path_buf());
+
+}
+
+/// Track a folder and its sub folders, so that adding or removing files expands macros again.
+///
+/// Stable macros can't track folders, so they are only tracked with the `nightly` feature.
+#[allow(unused_variables)]
+pub(crate) fn track_folder(folder: &Path) {
+
+    #[cfg(feature = "nightly")]
+    if proc_macro::is_available() && folder.is_dir() {
+        proc_macro::tracked::path(folder);
+        if let Ok(entries) = std::fs::read_dir(folder) {
+            for entry in entries.flatten().filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir())) {
+                track_folder(&entry.path());
+            }
+        }
+    }
+
+}
+
+/// Take inputs tracked since last call as tokens making the compiler track them on stable, `option_env!("KEY")`
+/// for env variables and `include_bytes!("file")` for files.
+pub(crate) fn take_tracking_tokens() -> TokenStream {
+
+    let env = std::mem::take(&mut *TRACKED_ENV.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    let files = std::mem::take(&mut *TRACKED_FILES.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+
+    let mut content = String::new();
+
+    for key in env {
+        content.push_str(format!("const _ : Option<&str> = option_env!({});", Literal::string(&key)).as_str());
+    }
+
+    for file in files {
+        content.push_str(format!("const _ : &[u8] = include_bytes!({});", Literal::string(&file.to_string_lossy())).as_str());
+    }
+
+    content.parse::<TokenStream>().unwrap()
+
+}
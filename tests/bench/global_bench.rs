@@ -0,0 +1,5 @@
+// Bench tests file linked by documentation examples.
+#[test]
+fn bench_test(){
+
+}
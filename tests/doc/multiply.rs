@@ -0,0 +1 @@
+assert_eq!(2 * 3, 6);
@@ -1,4 +1,4 @@
-// Test 007: Error same module name.
+// Test 007: Same module name get a hash suffix.
 use tests_bin::{ unit__tests };
 
 unit__tests!("base_test.rs");
@@ -0,0 +1,14 @@
+// Test 013 | Errors of multiple invocations are reported together
+use tests_bin::{ unit__tests, unit_tests };
+
+unit__tests!(foo.rs);
+unit__tests!("base_test.rs" "module");
+
+#[unit_tests("base_test.rs", "module", "Fafa")]
+pub fn foo(){
+    
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,11 @@
+// Test 014 | Error, file not found with suggestions.
+use tests_bin::{ unit_tests };
+
+#[unit_tests("ponyo/hamm.rs")]
+pub fn totoro(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,9 @@
+// Test 015 | unit__tests! work with glob patterns
+use tests_bin::{ unit__tests };
+
+unit__tests!("*.rs");
+unit__tests!("sf1/**/*.rs");
+
+fn main() {
+    
+}
@@ -0,0 +1,8 @@
+// Test 016 | Error, glob pattern matches no file.
+use tests_bin::{ unit__tests };
+
+unit__tests!("totoro/**/*.rs");
+
+fn main() {
+    
+}
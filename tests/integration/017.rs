@@ -0,0 +1,13 @@
+// Test 017 | unit__tests! work with folders
+use tests_bin::{ unit__tests, unit_tests };
+
+unit__tests!(dir = "sf1");
+
+#[unit_tests(dir = "ponyo", "ghibli")]
+pub fn totoro(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,8 @@
+// Test 018 | Error, folder not found.
+use tests_bin::{ unit__tests };
+
+unit__tests!(dir = "totoro");
+
+fn main() {
+    
+}
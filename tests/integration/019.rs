@@ -0,0 +1,11 @@
+// Test 019 | #unit_tests without parameters derive path from item
+use tests_bin::{ unit_tests };
+
+#[unit_tests]
+pub fn kiki(){
+
+}
+
+fn main() {
+    
+}
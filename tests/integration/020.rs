@@ -0,0 +1,13 @@
+// Test 020 | Error, #unit_tests without parameters on item without identifier
+use tests_bin::{ unit_tests };
+
+pub struct Kiki;
+
+#[unit_tests]
+impl Kiki {
+
+}
+
+fn main() {
+    
+}
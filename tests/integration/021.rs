@@ -0,0 +1,11 @@
+// Test 021 | #unit_tests without parameters use custom convention
+use tests_bin::{ unit_tests };
+
+#[unit_tests]
+pub fn jiji(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,18 @@
+// Test 022 | #unit_tests work in include mode with prelude
+use tests_bin::{ unit_tests };
+
+#[unit_tests("include/add.rs", mode = "include")]
+pub fn add(left: usize, right: usize) -> usize {
+    left + right
+}
+
+#[cfg(test)]
+pub mod helpers {
+    pub fn assert_three(value: usize) {
+        assert_eq!(value, 3);
+    }
+}
+
+fn main() {
+    
+}
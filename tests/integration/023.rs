@@ -0,0 +1,13 @@
+// Test 023 | unit__tests! and #unit_tests work with named parameters
+use tests_bin::{ unit__tests, unit_tests };
+
+unit__tests!(name = "named_super", path = "super_test.rs");
+
+#[unit_tests(path = "ponyo/ham.rs", name = "named_ham", attrs(allow(dead_code), doc = "Ham tests"))]
+pub fn totoro(){
+
+}
+
+fn main() {
+    
+}
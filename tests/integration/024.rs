@@ -0,0 +1,11 @@
+// Test 024 | Error, unknown named parameter.
+use tests_bin::{ unit_tests };
+
+#[unit_tests(pth = "ponyo/ham.rs")]
+pub fn totoro(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,18 @@
+// Test 025 | #unit_tests work with custom cfg predicates
+use tests_bin::{ unit__tests, unit_tests };
+
+unit__tests!("super_test.rs", "cfg_super", cfg = "all(test, not(feature = \"slow\"))");
+
+#[unit_tests("ponyo/ham.rs", "cfg_ham", cfg(any(test, doc)))]
+pub fn totoro(){
+
+}
+
+#[unit_tests("sf1/sf2/foo.rs", "cfg_foo", cfg(all(test, feature = "slow")))]
+pub fn foo(){
+    
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,11 @@
+// Test 026 | Error, incorrect cfg predicate.
+use tests_bin::{ unit_tests };
+
+#[unit_tests("ponyo/ham.rs", cfg(all(test feature)))]
+pub fn totoro(){
+
+}
+
+fn main() {
+    
+}
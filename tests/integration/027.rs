@@ -0,0 +1,13 @@
+// Test 027 | bench__tests! and #bench_tests work with bench cfg
+use tests_bin::{ bench__tests, bench_tests };
+
+bench__tests!("totoro.rs");
+
+#[bench_tests("totoro.rs")]
+pub fn totoro(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,21 @@
+// Test 028 | #doc_tests examples run with cargo test --doc, also on methods
+use tests_bin::{ doc_tests };
+
+#[doc_tests("add.md")]
+pub fn add(left: usize, right: usize) -> usize {
+    left + right
+}
+
+#[doc_tests("multiply.rs")]
+pub fn multiply(left: usize, right: usize) -> usize {
+    left * right
+}
+
+pub struct Calculator;
+
+impl Calculator {
+    #[doc_tests("add.md")]
+    pub fn add(left: usize, right: usize) -> usize {
+        left + right
+    }
+}
@@ -0,0 +1,11 @@
+// Test 029 | #unit_tests expose fixtures folder
+use tests_bin::{ unit_tests };
+
+#[unit_tests("codec/decode.rs", fixtures = "codec/decode/")]
+pub fn decode(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,11 @@
+// Test 030 | Error, fixtures folder not found.
+use tests_bin::{ unit_tests };
+
+#[unit_tests("codec/decode.rs", fixtures = "codec/encode/")]
+pub fn decode(){
+
+}
+
+fn main() {
+    
+}
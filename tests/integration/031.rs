@@ -0,0 +1,18 @@
+// Test 031 | #unit_tests module name generated from impl trait and type
+use std::fmt;
+use tests_bin::{ unit_tests };
+
+pub struct Point {
+    x : usize,
+}
+
+#[unit_tests("base_test.rs")]
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.x)
+    }
+}
+
+fn main() {
+    println!("{}", Point { x: 0 });
+}
@@ -0,0 +1,82 @@
+// Test 032 | #unit_tests work with every item kind
+use std::fmt;
+use tests_bin::{ unit_tests };
+
+#[unit_tests("base_test.rs")]
+#[derive(Clone, Copy)]
+pub struct Point<'a> {
+    x : &'a usize,
+}
+
+#[unit_tests("base_test.rs")]
+impl<'a> fmt::Display for Point<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.x)
+    }
+}
+
+#[unit_tests("base_test.rs")]
+impl<'a> Point<'a> {
+    pub fn x(&self) -> usize {
+        *self.x
+    }
+}
+
+#[unit_tests("base_test.rs")]
+pub trait Area {
+    fn area(&self) -> usize;
+}
+
+#[unit_tests("base_test.rs")]
+impl Area for (u8, u8) {
+    fn area(&self) -> usize {
+        self.0 as usize * self.1 as usize
+    }
+}
+
+#[unit_tests("base_test.rs", "tests_area_array")]
+impl Area for [u8; 4] {
+    fn area(&self) -> usize {
+        self[0] as usize * self[1] as usize
+    }
+}
+
+#[unit_tests("base_test.rs")]
+pub const ORIGIN : [u8; 4] = [0; 4];
+
+#[unit_tests("base_test.rs")]
+pub static mut COUNTER : usize = 0;
+
+#[unit_tests("base_test.rs")]
+pub type Distance = usize;
+
+#[unit_tests("base_test.rs")]
+pub union Bits {
+    value : u32,
+    float : f32,
+}
+
+#[unit_tests("base_test.rs")]
+unsafe extern "C" {
+    fn abs(input: i32) -> i32;
+}
+
+#[unit_tests("base_test.rs")]
+macro_rules! double {
+    ($value:expr) => { $value * 2 };
+}
+
+#[unit_tests("base_test.rs")]
+pub enum Shape {
+    Circle,
+}
+
+#[unit_tests("base_test.rs")]
+pub mod shapes {}
+
+fn main() {
+    let point = Point { x: &1 };
+    let bits = Bits { value: 0 };
+    let _ = (point.x(), ORIGIN, Shape::Circle, Distance::default(), unsafe { bits.float }, unsafe { abs(-1) }, double!(1));
+    println!("{}", point);
+}
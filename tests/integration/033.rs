@@ -0,0 +1,9 @@
+// Test 033 | Error : #unit_tests on unsupported item.
+use tests_bin::{ unit_tests };
+
+#[unit_tests("base_test.rs")]
+use std::fmt;
+
+fn main() {
+    
+}
@@ -0,0 +1,41 @@
+// Test 034 | #unit_tests on impl block link methods tests
+use std::fmt;
+use tests_bin::{ unit_tests };
+
+pub struct Point {
+    x : usize,
+    y : usize,
+}
+
+#[unit_tests]
+impl Point {
+    #[unit_test_file("base_test.rs")]
+    #[unit_test_file("kiki.rs")]
+    pub fn len(&self) -> usize {
+        self.x + self.y
+    }
+
+    /// Area of point.
+    #[inline]
+    #[unit_test_file("base_test.rs", "point_area_tests")]
+    pub fn area(&self) -> usize {
+        self.x * self.y
+    }
+
+    pub fn origin() -> Point {
+        Point { x: 0, y: 0 }
+    }
+}
+
+#[unit_tests("base_test.rs")]
+impl fmt::Display for Point {
+    #[unit_test_file(path = "base_test.rs")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.x, self.y)
+    }
+}
+
+fn main() {
+    let point = Point::origin();
+    println!("{} {} {}", point, point.len(), point.area());
+}
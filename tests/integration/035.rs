@@ -0,0 +1,15 @@
+// Test 035 | unit__tests! and #unit_tests work with raw strings and string macros
+use tests_bin::{ unit__tests, unit_tests };
+
+unit__tests!(r"super_test.rs", r#"raw_super"#);
+unit__tests!(path = concat!("ponyo", "/", "ham.rs"), name = "concat_ham");
+unit__tests!(concat!(env!("TESTS_BIN_PREFIX"), "_test.rs"), "env_base");
+
+#[unit_tests(path = "ponyo\x2Fham.rs", name = "escaped_ham")]
+pub fn totoro(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,16 @@
+// Test 036 | Error : Parameters that aren't strings.
+use tests_bin::{ unit__tests, unit_tests };
+
+unit__tests!(42);
+unit__tests!(b"base_test.rs");
+unit__tests!(concat!("base", stringify!(_test)), "stringified");
+unit__tests!(env!("TESTS_BIN_UNSET"));
+
+#[unit_tests(include_str!("base_test.rs"))]
+pub fn foo(){
+    
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,6 @@
+// Test 037 | Paths are relative to nested source files
+mod physics;
+
+fn main() {
+    physics::collide();
+}
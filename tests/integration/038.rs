@@ -0,0 +1,11 @@
+// Test 038 | Nested source file `src/physics/mod.rs` of test 037
+use tests_bin::{ unit__tests, unit_tests };
+
+mod shapes;
+
+unit__tests!(dir = "sf1");
+
+#[unit_tests("codec/decode.rs", fixtures = "codec/decode/")]
+pub fn collide(){
+    shapes::area();
+}
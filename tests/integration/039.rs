@@ -0,0 +1,7 @@
+// Test 039 | Nested non mod-rs source file `src/physics/shapes.rs` of test 037
+use tests_bin::{ unit_tests };
+
+#[unit_tests("base_test.rs")]
+pub fn area(){
+
+}
@@ -0,0 +1,13 @@
+// Test 040 | Error : Paths outside of tests bin folder.
+use tests_bin::{ unit__tests, unit_tests };
+
+unit__tests!("../shared/base_test.rs");
+
+#[unit_tests("/etc/hosts")]
+pub fn foo(){
+    
+}
+
+fn main() {
+    
+}
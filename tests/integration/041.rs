@@ -0,0 +1,11 @@
+// Test 041 | Paths outside of tests bin folder are allowed with confine policy.
+use tests_bin::{ unit__tests };
+
+unit__tests!(path = "../shared/base_test.rs", name = "shared_base", confine = "none");
+
+#[cfg(unix)]
+unit__tests!(path = "shared_link.rs", confine = "lexical");
+
+fn main() {
+    
+}
@@ -0,0 +1,8 @@
+// Test 042 | Error : Symbolic link leading outside of tests bin folder.
+use tests_bin::{ unit__tests };
+
+unit__tests!("shared_link.rs");
+
+fn main() {
+    
+}
@@ -0,0 +1,11 @@
+// Test 043 | Settings are read from env, tests_bin.toml and Cargo.toml metadata in that order.
+use tests_bin::{ unit_tests };
+
+#[unit_tests]
+pub fn jiji(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,11 @@
+// Test 044 | Workspace members read settings from workspace root Cargo.toml metadata.
+use tests_bin::{ unit_tests };
+
+#[unit_tests]
+pub fn chihiro(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,13 @@
+// Test 045 | unit__tests! and #unit_tests work with named roots.
+use tests_bin::{ unit__tests, unit_tests };
+
+unit__tests!(root = "golden", path = "howl.rs", name = "golden_howl");
+
+#[unit_tests(root = "golden", path = "howl.rs")]
+pub fn howl(){
+
+}
+
+fn main() {
+    
+}
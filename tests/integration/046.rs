@@ -0,0 +1,11 @@
+// Test 046 | Error : Named root not declared.
+use tests_bin::{ unit_tests };
+
+#[unit_tests(root = "silver", path = "howl.rs")]
+pub fn howl(){
+
+}
+
+fn main() {
+    
+}
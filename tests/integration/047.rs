@@ -0,0 +1,11 @@
+// Test 047 | Each target has its own tests bin folder with {target} placeholder.
+use tests_bin::{ unit_tests };
+
+#[unit_tests]
+pub fn kiki(){
+
+}
+
+fn main() {
+    
+}
@@ -0,0 +1,9 @@
+// Test 048 | unit__tests! and #unit_tests work outside of cargo with bare rustc.
+use tests_bin::{ unit__tests, unit_tests };
+
+unit__tests!("porco.rs", "rustc_porco");
+
+#[unit_tests]
+pub fn porco(){
+
+}
@@ -0,0 +1,8 @@
+// Test 049 | Changing configuration expands macros again without cleaning.
+use tests_bin::{ unit__tests };
+
+unit__tests!("chihiro.rs");
+
+fn main() {
+    
+}
@@ -0,0 +1,6 @@
+// Test 050 | unit_tests_auto! links every file of the tests bin folder watched by tests_bin_build.
+tests_bin::unit_tests_auto!();
+
+fn main() {
+    
+}
@@ -0,0 +1,8 @@
+// Test 051 | unit_tests_mirror! links the mirror tests file of each source file.
+mod ponyo;
+
+tests_bin::unit_tests_mirror!();
+
+fn main() {
+    
+}
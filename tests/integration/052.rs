@@ -0,0 +1,13 @@
+// Test 052 | Macros in inline modules and folders with inner attributes.
+tests_bin::unit__tests!(dir = "phys");
+
+mod inner {
+    #[tests_bin::unit_tests("base_test.rs")]
+    pub fn ponyo() {}
+
+    tests_bin::unit__tests!(dir = "phys", "inner_phys");
+}
+
+fn main() {
+    inner::ponyo();
+}
@@ -0,0 +1,6 @@
+// Test 053 | Error : `=` before attrs(...) group.
+tests_bin::unit__tests!("super_test.rs", attrs = (allow(dead_code)), name "zz");
+
+fn main() {
+    
+}
@@ -0,0 +1,11 @@
+// Test 054 | Tests file with fixtures in module mode keeps inner attributes and nested modules.
+use tests_bin::unit_tests;
+
+#[unit_tests("heat/heat.rs", fixtures = "codec/decode/")]
+pub fn heat() -> u32 {
+    3
+}
+
+fn main() {
+    heat();
+}
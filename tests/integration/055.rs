@@ -0,0 +1,15 @@
+// Test 055 | Same module name in sibling inline modules has no hash suffix.
+mod a {
+    #[tests_bin::unit_tests("base_test.rs")]
+    pub fn add() {}
+}
+
+mod b {
+    #[tests_bin::unit_tests("base_test.rs")]
+    pub fn add() {}
+}
+
+fn main() {
+    a::add();
+    b::add();
+}
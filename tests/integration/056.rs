@@ -0,0 +1,13 @@
+// Test 056 | Paths of macros in inline modules are relative to the folder implied by the modules.
+mod tools {
+    #[tests_bin::unit_tests("base_test.rs")]
+    pub fn add() {}
+
+    #[tests_bin::unit_tests("codec/decode.rs", fixtures = "codec/decode/")]
+    pub fn decode() {}
+}
+
+fn main() {
+    tools::add();
+    tools::decode();
+}
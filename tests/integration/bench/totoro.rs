@@ -0,0 +1,4 @@
+#[test]
+fn bench_test1(){
+
+}
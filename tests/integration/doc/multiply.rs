@@ -0,0 +1 @@
+assert_eq!(_tb_integration::multiply(2, 3), 6);
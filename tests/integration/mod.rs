@@ -1,7 +1,7 @@
 use std::path::Path;
 
 // Contains tests_bin integration tests for Linux, Windows and Macos
-use crate::{ init_integration_test, clean_integration_test, run_test, copy_dir_all};
+use crate::{ SETTINGS_PKG_NAME, init_integration_test, clean_integration_test, run_test, copy_dir_all, write_file, remove_file, copy_file, append_file, assert_cmd, run_command, run_command_outside_cargo};
 
 /// Integration tests project name
 const PRJ_NAME : &str = "_tb_integration";
@@ -15,15 +15,64 @@ const PRJ_NAME : &str = "_tb_integration";
 /// V4 | TestsBinErrors::IncorrectParameters > Parameters mixed with idents.
 /// V5 | File not found.
 /// V6 | Copy unit tests files to tests/unit
-/// V7 | Error : Same module name.
+/// V7 | Same module name get a hash suffix.
 /// V8 | unit__tests! work without module name
-/// V9 | run Cargo test.
-/// V10 | unit__tests! work with module name 
-/// V11 | run Cargo test.
-/// V12 | #unit_tests work without module name
-/// V13 | run Cargo test.
-/// V14 | #unit_tests work with module name 
-/// V15 | run Cargo test.
+/// V9 | unit__tests! work with module name 
+/// V10 | #unit_tests work without module name
+/// V11 | #unit_tests work with module name 
+/// V12 | unit__tests! and #unit_tests work together
+/// V13 | Errors of multiple invocations are reported together.
+/// V14 | File not found suggests nearest files.
+/// V15 | unit__tests! work with glob patterns
+/// V16 | Error : Glob pattern matches no file.
+/// V17 | unit__tests! and #unit_tests work with folders
+/// V18 | Error : Folder not found.
+/// V19 | #unit_tests without parameters derive path from item
+/// V20 | Error : #unit_tests without parameters on item without identifier
+/// V21 | #unit_tests without parameters use custom convention
+/// V22 | #unit_tests work in include mode with prelude
+/// V23 | unit__tests! and #unit_tests work with named parameters
+/// V24 | Error : Unknown named parameter.
+/// V25 | unit__tests! and #unit_tests work with custom cfg predicates
+/// V26 | Error : Incorrect cfg predicate.
+/// V27 | Default cfg predicate is customizable.
+/// V28 | Copy bench tests files to tests/bench
+/// V29 | bench__tests! and #bench_tests work with bench cfg
+/// V30 | Copy doc tests files to tests/doc
+/// V31 | #doc_tests examples run with cargo test --doc, also on methods
+/// V32 | #unit_tests expose fixtures folder
+/// V33 | Error : Fixtures folder not found.
+/// V34 | #unit_tests module name generated from impl trait and type.
+/// V35 | #unit_tests work with every item kind.
+/// V36 | Error : #unit_tests on unsupported item.
+/// V37 | #unit_tests on impl block link methods tests.
+/// V38 | unit__tests! and #unit_tests work with raw strings and string macros.
+/// V39 | Error : Parameters that aren't strings.
+/// V40 | Paths are relative to nested source files.
+/// V41 | Error : Paths outside of tests bin folder.
+/// V42 | Paths outside of tests bin folder are allowed with confine policy.
+/// V43 | Error : Symbolic link leading outside of tests bin folder.
+/// V44 | Settings are read from env, tests_bin.toml and Cargo.toml metadata in that order, unsupported syntax is an error.
+/// V45 | Workspace members read settings from workspace metadata with {workspace} and {crate} placeholders, excluded crates don't.
+/// V46 | unit__tests! and #unit_tests work with named roots.
+/// V47 | Error : Named root not declared.
+/// V48 | Each target has its own tests bin folder with {target} placeholder.
+/// V49 | Warning : Tests file linked from two targets found by tests_bin_build, nothing is written in target folder.
+/// V50 | Project root is the parent of `src` outside of cargo with bare rustc.
+/// V51 | Project root is given by TESTS_BIN_ROOT outside of cargo.
+/// V52 | Project root is found with a root marker file outside of cargo.
+/// V53 | Changing env settings expands macros again without cleaning.
+/// V54 | Changing settings files expands macros again without cleaning.
+/// V55 | unit_tests_auto! links every file of the tests bin folder watched by tests_bin_build, from env or settings files.
+/// V56 | Error : unit_tests_auto! without module tree.
+/// V57 | unit_tests_mirror! links mirror tests files and warns on mismatches.
+/// V58 | Error : unit_tests_mirror! mismatches reported as errors.
+/// V59 | Macros work in inline modules and folders files can have inner attributes.
+/// V60 | Error : `=` before `attrs(...)` group.
+/// V61 | Default `cfg(bench)` warns as unexpected until declared in `[lints.rust]`.
+/// V62 | Tests file with fixtures in module mode keeps inner attributes and nested modules.
+/// V63 | Same module name in sibling inline modules has no hash suffix.
+/// V64 | Paths of macros in inline modules are relative to the folder implied by the modules.
 #[test]
 fn integration_tests() {
     // Get integration test working path and project path.
@@ -42,17 +91,7 @@ fn integration_tests() {
     run_test(&working_path, &project_path, "integration/004.rs", false, "Incorrect parameters!");
 
     // V5 | File not found.
-    // Windows only instruction
-    #[cfg(windows)]
-    {
-        run_test(&working_path, &project_path, "integration/005.rs", false, "os error 3");
-    }
-
-    // All other Os
-    #[cfg(not(windows))]
-    {
-        run_test(&working_path, &project_path, "integration/005.rs", false, "os error 2");
-    }
+    run_test(&working_path, &project_path, "integration/005.rs", false, "Tests file `foo.rs` not found in `tests/unit`!");
     
     // V6 | Copy unit tests files to tests/unit
     let tests_path = format!("{}/tests/integration/unit", working_path);
@@ -62,8 +101,8 @@ fn integration_tests() {
         Err(err) => panic!("{:?}", err),    // Panic if we can't copy package directory.
     }
 
-    // V7 | Error : Same module name.
-    run_test(&working_path, &project_path, "integration/007.rs", false, "is defined multiple times");
+    // V7 | Same module name get a hash suffix.
+    run_test(&working_path, &project_path, "integration/007.rs", true, "test base_test_rs_");
 
     // V8 | unit__tests! work without module name
     run_test(&working_path, &project_path, "integration/008.rs", true, "Finished");
@@ -80,6 +119,397 @@ fn integration_tests() {
     // V12 | unit__tests! and #unit_tests work together
     run_test(&working_path, &project_path, "integration/012.rs", true, "Finished");
 
+    // V13 | Errors of multiple invocations are reported together.
+    run_test(&working_path, &project_path, "integration/013.rs", false, "due to 5 previous errors");
+
+    // V14 | File not found suggests nearest files.
+    run_test(&working_path, &project_path, "integration/014.rs", false, "Did you mean `ponyo/ham.rs`?");
+
+    // V15 | unit__tests! work with glob patterns
+    run_test(&working_path, &project_path, "integration/015.rs", true, "test sf1_sf2_foo_rs::unit_test1 ... ok");
+
+    // V16 | Error : Glob pattern matches no file.
+    run_test(&working_path, &project_path, "integration/016.rs", false, "Glob pattern `totoro/**/*.rs` matches no tests file");
+
+    // V17 | unit__tests! and #unit_tests work with folders
+    run_test(&working_path, &project_path, "integration/017.rs", true, "test sf1::sf2::foo::unit_test1 ... ok");
+
+    // V18 | Error : Folder not found.
+    run_test(&working_path, &project_path, "integration/018.rs", false, "Tests folder `totoro` not found in `tests/unit`!");
+
+    // V19 | #unit_tests without parameters derive path from item
+    run_test(&working_path, &project_path, "integration/019.rs", true, "test tests_fn_kiki::unit_test1 ... ok");
+
+    // V20 | Error : #unit_tests without parameters on item without identifier
+    run_test(&working_path, &project_path, "integration/020.rs", false, "Can't derive tests path from an item without identifier!");
+
+    // V21 | #unit_tests without parameters use custom convention
+    let cargo_config = format!("{}/.cargo/config.toml", project_path);
+    write_file(cargo_config.clone(), "[env]\ntests_bin-convention = \"{kind}/{ident}.rs\"\n");
+    run_test(&working_path, &project_path, "integration/021.rs", true, "test tests_fn_jiji::unit_test1 ... ok");
+    remove_file(cargo_config.clone());
+
+    // V22 | #unit_tests work in include mode with prelude
+    write_file(cargo_config.clone(), "[env]\ntests_bin-prelude = \"use crate::helpers::*; // helpers\"\n");
+    run_test(&working_path, &project_path, "integration/022.rs", true, "test tests_fn_add::add_works ... ok");
+    remove_file(cargo_config.clone());
+
+    // V23 | unit__tests! and #unit_tests work with named parameters
+    run_test(&working_path, &project_path, "integration/023.rs", true, "test named_ham::unit_test1 ... ok");
+
+    // V24 | Error : Unknown named parameter.
+    run_test(&working_path, &project_path, "integration/024.rs", false, "Unknown parameter `pth`, should be one of `path`, `name`, `dir`, `mode`, `attrs`, `cfg`, `fixtures`, `confine`, `root`.");
+
+    // V25 | unit__tests! and #unit_tests work with custom cfg predicates
+    run_test(&working_path, &project_path, "integration/025.rs", true, "test cfg_super::unit_test1 ... ok");
+
+    // V26 | Error : Incorrect cfg predicate.
+    run_test(&working_path, &project_path, "integration/026.rs", false, "Incorrect cfg predicate `all(test feature)`!");
+
+    // V27 | Default cfg predicate is customizable.
+    write_file(cargo_config.clone(), "[env]\ntests_bin-cfg = \"all(test, not(feature = \\\"slow\\\"))\"\n");
+    run_test(&working_path, &project_path, "integration/008.rs", true, "test base_test_rs::unit_test1 ... ok");
+    remove_file(cargo_config.clone());
+
+    // V28 | Copy bench tests files to tests/bench
+    let bench_path = format!("{}/tests/integration/bench", working_path);
+    let dest_path = format!("{}/tests/bench", project_path);
+    match copy_dir_all(Path::new(&bench_path), Path::new(&dest_path)){
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't copy bench directory.
+    }
+
+    // V29 | bench__tests! and #bench_tests work with bench cfg
+    write_file(cargo_config.clone(), "[build]\nrustflags = [\"--cfg\", \"bench\"]\n");
+    run_test(&working_path, &project_path, "integration/027.rs", true, "test bench_fn_totoro::bench_test1 ... ok");
+    remove_file(cargo_config.clone());
+
+    // V30 | Copy doc tests files to tests/doc
+    let doc_path = format!("{}/tests/integration/doc", working_path);
+    let dest_path = format!("{}/tests/doc", project_path);
+    match copy_dir_all(Path::new(&doc_path), Path::new(&dest_path)){
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't copy doc directory.
+    }
+
+    // V31 | #doc_tests examples run with cargo test --doc, also on methods
+    let lib_path = format!("{}/src/lib.rs", project_path);
+    copy_file(format!("{}/tests/integration/028.rs", working_path), lib_path.clone());
+    assert_cmd!(&project_path, "cargo", ["test", "--doc"], true, "test result: ok. 3 passed");
+    remove_file(lib_path);
+
+    // V32 | #unit_tests expose fixtures folder
+    run_test(&working_path, &project_path, "integration/029.rs", true, "test tests_fn_decode::tests::decode_fixture ... ok");
+
+    // V33 | Error : Fixtures folder not found.
+    run_test(&working_path, &project_path, "integration/030.rs", false, "Fixtures folder `codec/encode/` not found in `tests/unit`!");
+
+    // V34 | #unit_tests module name generated from impl trait and type.
+    run_test(&working_path, &project_path, "integration/031.rs", true, "test tests_impl_Display_for_Point::unit_test1 ... ok");
+
+    // V35 | #unit_tests work with every item kind.
+    run_test(&working_path, &project_path, "integration/032.rs", true, "test result: ok. 42 passed");
+    for module in ["tests_struct_Point", "tests_impl_Point", "tests_trait_Area", "tests_impl_Area", "tests_area_array", "tests_const_ORIGIN", "tests_static_COUNTER", "tests_type_Distance",
+        "tests_union_Bits", "tests_extern_C", "tests_macro_rules_double", "tests_enum_Shape", "tests_mod_shapes"] {
+        assert_cmd!(&project_path, "cargo", ["test"], true, format!("test {}::unit_test1 ... ok", module).as_str());
+    }
+
+    // V36 | Error : #unit_tests on unsupported item.
+    run_test(&working_path, &project_path, "integration/033.rs", false, "Unsupported item `use`!");
+
+    // V37 | #unit_tests on impl block link methods tests.
+    run_test(&working_path, &project_path, "integration/034.rs", true, "test result: ok. 13 passed");
+    for module in ["tests_Point_len", "point_area_tests", "tests_impl_Display_for_Point", "tests_Point_fmt"] {
+        assert_cmd!(&project_path, "cargo", ["test"], true, format!("test {}::unit_test1 ... ok", module).as_str());
+    }
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test tests_Point_len_");    // Second file of a method gets a hash suffix
+
+    // V38 | unit__tests! and #unit_tests work with raw strings and string macros.
+    write_file(cargo_config.clone(), "[env]\nTESTS_BIN_PREFIX = \"base\"\n");
+    run_test(&working_path, &project_path, "integration/035.rs", true, "test raw_super::unit_test1 ... ok");
+    for module in ["concat_ham", "env_base", "escaped_ham"] {
+        assert_cmd!(&project_path, "cargo", ["test"], true, format!("test {}::unit_test1 ... ok", module).as_str());
+    }
+    remove_file(cargo_config.clone());
+
+    // V39 | Error : Parameters that aren't strings.
+    run_test(&working_path, &project_path, "integration/036.rs", false, "Incorrect parameter `42`!");
+    for expected in ["Incorrect parameter `b\"base_test.rs\"`!", "Unsupported macro `stringify!`!", "Env variable `TESTS_BIN_UNSET` not set!", "Unsupported macro `include_str!`!"] {
+        assert_cmd!(&project_path, "cargo", ["test"], false, expected);
+    }
+
+    // V40 | Paths are relative to nested source files.
+    let physics_path = format!("{}/src/physics", project_path);
+    write_file(format!("{}/mod.rs", physics_path), "");
+    copy_file(format!("{}/tests/integration/038.rs", working_path), format!("{}/mod.rs", physics_path));
+    copy_file(format!("{}/tests/integration/039.rs", working_path), format!("{}/shapes.rs", physics_path));
+    run_test(&working_path, &project_path, "integration/037.rs", true, "test physics::sf1::sf2::foo::unit_test1 ... ok");
+    for expected in ["test physics::tests_fn_collide::tests::decode_fixture ... ok", "test physics::shapes::tests_fn_area::unit_test1 ... ok"] {
+        assert_cmd!(&project_path, "cargo", ["test"], true, expected);
+    }
+    match std::fs::remove_dir_all(physics_path){
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't delete physics directory.
+    }
+
+    // V41 | Error : Paths outside of tests bin folder.
+    let shared_path = format!("{}/tests/shared/base_test.rs", project_path);
+    write_file(shared_path.clone(), "");
+    copy_file(format!("{}/tests/integration/unit/base_test.rs", working_path), shared_path.clone());
+    run_test(&working_path, &project_path, "integration/040.rs", false, "Tests path `../shared/base_test.rs` is outside of `tests/unit`!");
+    assert_cmd!(&project_path, "cargo", ["test"], false, "Tests path `/etc/hosts` is outside of `tests/unit`!");
+
+    // V42 | Paths outside of tests bin folder are allowed with confine policy.
+    let link_path = format!("{}/tests/unit/shared_link.rs", project_path);
+    #[cfg(unix)]
+    match std::os::unix::fs::symlink(&shared_path, &link_path) {
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't create symbolic link.
+    }
+    run_test(&working_path, &project_path, "integration/041.rs", true, "test shared_base::unit_test1 ... ok");
+    #[cfg(unix)]
+    {
+        assert_cmd!(&project_path, "cargo", ["test"], true, "test shared_link_rs::unit_test1 ... ok");
+
+        // V43 | Error : Symbolic link leading outside of tests bin folder.
+        run_test(&working_path, &project_path, "integration/042.rs", false, "Tests path `shared_link.rs` is a symbolic link leading outside of `tests/unit`!");
+    }
+    remove_file(link_path);
+    remove_file(shared_path);
+
+    // V44 | Settings are read from env, tests_bin.toml and Cargo.toml metadata in that order, unsupported syntax is an error.
+    let manifest_path = format!("{}/Cargo.toml", project_path);
+    let settings_path = format!("{}/tests_bin.toml", project_path);
+    let manifest = match std::fs::read_to_string(&manifest_path) {
+        Ok(manifest) => manifest,
+        Err(err) => panic!("{:?}", err),    // Panic if we can't read Cargo.toml
+    };
+    write_file(manifest_path.clone(), format!("{}\n[package.metadata.tests_bin]\nprefix = \"meta\"\nconvention = \"{{kind}}/{{ident}}.rs\"\n", manifest).as_str());
+    run_test(&working_path, &project_path, "integration/043.rs", true, "test meta_fn_jiji::unit_test1 ... ok");
+    write_file(settings_path.clone(), "prefix = 'file' # Override metadata\n");
+    run_test(&working_path, &project_path, "integration/043.rs", true, "test file_fn_jiji::unit_test1 ... ok");
+    write_file(cargo_config.clone(), "[env]\ntests_bin-prefix = \"env\"\n");
+    run_test(&working_path, &project_path, "integration/043.rs", true, "test env_fn_jiji::unit_test1 ... ok");
+    remove_file(cargo_config.clone());
+    remove_file(settings_path.clone());
+    write_file(manifest_path.clone(), format!("{}\n[package.metadata]\ntests_bin = {{ prefix = \"meta\" }}\n", manifest).as_str());
+    run_test(&working_path, &project_path, "integration/043.rs", false, "Unsupported settings `tests_bin = { prefix = \"meta\" }` in `Cargo.toml`!");
+
+    // V45 | Workspace members read settings from workspace metadata with {workspace} and {crate} placeholders, excluded crates don't.
+    let member_path = format!("{}/member", project_path);
+    let package_name = format!("{}-{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    write_file(manifest_path.clone(), format!("{}\n[workspace]\nmembers = [\"member\"]\nexclude = [\"excluded\"]\n\n[workspace.metadata.tests_bin]\nfolder = \"{{workspace}}/tests/unit/{{crate}}\"\nprefix = \"ws\"\n", manifest).as_str());
+    write_file(format!("{}/Cargo.toml", member_path), format!("[package]\nname = \"member\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\ntests_bin = {{ path = \"../{}\" }}\n", package_name).as_str());
+    write_file(format!("{}/src/main.rs", member_path), "");
+    copy_file(format!("{}/tests/integration/044.rs", working_path), format!("{}/src/main.rs", member_path));
+    write_file(format!("{}/tests/unit/member/chihiro.rs", project_path), "");
+    copy_file(format!("{}/tests/integration/unit/base_test.rs", working_path), format!("{}/tests/unit/member/chihiro.rs", project_path));
+    assert_cmd!(&member_path, "cargo", ["test"], true, "test ws_fn_chihiro::unit_test1 ... ok");
+    write_file(format!("{}/tests_bin.toml", member_path), "prefix = \"member\"\n");
+    assert_cmd!(&member_path, "cargo", ["clean"], true, "");   // Created settings files aren't tracked
+    assert_cmd!(&member_path, "cargo", ["test"], true, "test member_fn_chihiro::unit_test1 ... ok");
+    let excluded_path = format!("{}/excluded", project_path);
+    write_file(format!("{}/Cargo.toml", excluded_path), format!("[package]\nname = \"excluded\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\ntests_bin = {{ path = \"../{}\" }}\n\n[patch.crates-io]\n{} = {{ path = \"{}/{}\" }}\n", 
+        package_name, SETTINGS_PKG_NAME, working_path, SETTINGS_PKG_NAME).as_str());
+    write_file(format!("{}/src/main.rs", excluded_path), "");
+    copy_file(format!("{}/tests/integration/044.rs", working_path), format!("{}/src/main.rs", excluded_path));
+    write_file(format!("{}/tests/unit/chihiro.rs", excluded_path), "");
+    copy_file(format!("{}/tests/integration/unit/base_test.rs", working_path), format!("{}/tests/unit/chihiro.rs", excluded_path));
+    assert_cmd!(&excluded_path, "cargo", ["test"], true, "test tests_fn_chihiro::unit_test1 ... ok");
+    for path in [member_path, excluded_path] {
+        match std::fs::remove_dir_all(&path) {
+            Ok(_) => {},
+            Err(err) => panic!("{:?}", err),    // Panic if we can't remove member folders.
+        }
+    }
+    write_file(manifest_path.clone(), manifest.as_str());
+
+    // V46 | unit__tests! and #unit_tests work with named roots.
+    write_file(format!("{}/tests/golden/howl.rs", project_path), "");
+    copy_file(format!("{}/tests/integration/unit/base_test.rs", working_path), format!("{}/tests/golden/howl.rs", project_path));
+    write_file(cargo_config.clone(), "[env]\ntests_bin-root-golden-folder = \"tests/golden\"\ntests_bin-root-golden-prefix = \"gold\"\n");
+    run_test(&working_path, &project_path, "integration/045.rs", true, "test gold_fn_howl::unit_test1 ... ok");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test golden_howl::unit_test1 ... ok");
+
+    // V47 | Error : Named root not declared.
+    run_test(&working_path, &project_path, "integration/046.rs", false, "Tests root `silver` not declared! Should be declared with `root-silver-folder` setting.");
+    remove_file(cargo_config.clone());
+
+    // V48 | Each target has its own tests bin folder with {target} placeholder.
+    let bin_path = format!("{}/src/bin/sosuke.rs", project_path);
+    write_file(bin_path.clone(), "");
+    copy_file(format!("{}/tests/integration/047.rs", working_path), bin_path.clone());
+    write_file(format!("{}/tests/target/{}/kiki.rs", project_path, PRJ_NAME), "");
+    copy_file(format!("{}/tests/integration/unit/base_test.rs", working_path), format!("{}/tests/target/{}/kiki.rs", project_path, PRJ_NAME));
+    write_file(format!("{}/tests/target/sosuke/kiki.rs", project_path), "#[test]\nfn sosuke_test(){\n\n}\n");
+    write_file(cargo_config.clone(), "[env]\ntests_bin-folder = \"tests/target/{target}\"\n");
+    run_test(&working_path, &project_path, "integration/047.rs", true, "test tests_fn_kiki::sosuke_test ... ok");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test tests_fn_kiki::unit_test1 ... ok");
+    remove_file(cargo_config.clone());
+
+    // V49 | Warning : Tests file linked from two targets found by tests_bin_build, nothing is written in target folder.
+    let build_path = format!("{}/build.rs", project_path);
+    write_file(build_path.clone(), "fn main() {\n    tests_bin_build::generate();\n}\n");
+    write_file(manifest_path.clone(), format!("{}\n[build-dependencies]\ntests_bin_build = {{ path = \"{}/tests_bin_build\" }}\n", manifest, working_path).as_str());
+    run_test(&working_path, &project_path, "integration/047.rs", true, "Tests file `tests/unit/kiki.rs` is linked from targets `src/bin/sosuke.rs`, `src/main.rs`!");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test tests_fn_kiki::unit_test1 ... ok");
+    assert!(!Path::new(&format!("{}/target/tests_bin", project_path)).exists(), "Expected no tests_bin folder in target folder!");
+    remove_file(build_path);
+    write_file(manifest_path.clone(), manifest.as_str());
+    remove_file(bin_path);
+
+    // V50 | Project root is the parent of `src` outside of cargo with bare rustc.
+    let rustc_path = format!("{}/rustc", project_path);
+    let deps_path = format!("{}/deps", rustc_path);
+    write_file(format!("{}/src/lib.rs", rustc_path), "");
+    copy_file(format!("{}/tests/integration/048.rs", working_path), format!("{}/src/lib.rs", rustc_path));
+    write_file(format!("{}/tests/unit/porco.rs", rustc_path), "");
+    copy_file(format!("{}/tests/integration/unit/base_test.rs", working_path), format!("{}/tests/unit/porco.rs", rustc_path));
+    let result = run_command_outside_cargo(&working_path, "rustc", vec!["--crate-type", "rlib", "--edition", "2021", "--crate-name", "tests_bin_settings", 
+        "--out-dir", &deps_path, "tests_bin_settings/src/lib.rs"], vec![]);
+    assert!(result.0, "Expected tests_bin_settings to build with rustc!\n{}", result.1);
+    let result = run_command_outside_cargo(&working_path, "rustc", vec!["--crate-type", "proc-macro", "--edition", "2021", "--crate-name", "tests_bin", 
+        "--extern", "proc_macro", "-L", &deps_path, "--extern", "tests_bin_settings", "--out-dir", &deps_path, "src/lib.rs"], vec![]);
+    assert!(result.0, "Expected tests_bin to build with rustc!\n{}", result.1);
+    let rustc_test = |source : &str, envs : Vec<(&str, &str)>| {
+        let result = run_command_outside_cargo(&project_path, "rustc", vec!["--test", "--edition", "2021", "-L", &deps_path, "--extern", "tests_bin", 
+            "-o", &format!("{}/porco", rustc_path), source], envs);
+        assert!(result.0, "Expected `{}` to build with rustc!\n{}", source, result.1);
+        let result = run_command_outside_cargo(&project_path, &format!("{}/porco", rustc_path), vec![], vec![]);
+        assert!(result.0 && result.1.contains("test tests_fn_porco::unit_test1 ... ok") && result.1.contains("test rustc_porco::unit_test1 ... ok"), 
+            "Expected `{}` tests to pass!\n{}", source, result.1);
+    };
+    rustc_test("rustc/src/lib.rs", vec![]);
+
+    // V51 | Project root is given by TESTS_BIN_ROOT outside of cargo.
+    write_file(format!("{}/flat/lib.rs", rustc_path), "");
+    copy_file(format!("{}/tests/integration/048.rs", working_path), format!("{}/flat/lib.rs", rustc_path));
+    rustc_test("rustc/flat/lib.rs", vec![("TESTS_BIN_ROOT", "rustc")]);
+
+    // V52 | Project root is found with a root marker file outside of cargo.
+    write_file(format!("{}/flat/tests_bin.toml", rustc_path), "folder = \"../tests/unit\"\n");
+    rustc_test("rustc/flat/lib.rs", vec![]);
+    match std::fs::remove_dir_all(&rustc_path) {
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't remove rustc folder.
+    }
+
+    // V53 | Changing env settings expands macros again without cleaning.
+    write_file(format!("{}/tests/track/a/chihiro.rs", project_path), "#[test]\nfn track_a(){\n\n}\n");
+    write_file(format!("{}/tests/track/b/chihiro.rs", project_path), "#[test]\nfn track_b(){\n\n}\n");
+    write_file(cargo_config.clone(), "[env]\ntests_bin-folder = \"tests/track/a\"\n");
+    run_test(&working_path, &project_path, "integration/049.rs", true, "test chihiro_rs::track_a ... ok");
+    write_file(cargo_config.clone(), "[env]\ntests_bin-folder = \"tests/track/b\"\n");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test chihiro_rs::track_b ... ok");
+
+    // V54 | Changing settings files expands macros again without cleaning.
+    remove_file(cargo_config.clone());
+    write_file(settings_path.clone(), "folder = \"tests/track/a\"\n");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test chihiro_rs::track_a ... ok");
+    write_file(settings_path.clone(), "folder = \"tests/track/b\"\n");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test chihiro_rs::track_b ... ok");
+    remove_file(settings_path.clone());
+
+    // V55 | unit_tests_auto! links every file of the tests bin folder watched by tests_bin_build, from env or settings files.
+    let build_path = format!("{}/build.rs", project_path);
+    write_file(build_path.clone(), "fn main() {\n    tests_bin_build::generate();\n}\n");
+    write_file(manifest_path.clone(), format!("{}\n[build-dependencies]\ntests_bin_build = {{ path = \"{}/tests_bin_build\" }}\n", manifest, working_path).as_str());
+    write_file(format!("{}/tests/auto/totoro.rs", project_path), "");
+    copy_file(format!("{}/tests/integration/unit/base_test.rs", working_path), format!("{}/tests/auto/totoro.rs", project_path));
+    write_file(format!("{}/tests/auto/forest/catbus.rs", project_path), "#[test]\nfn catbus(){\n\n}\n");
+    write_file(cargo_config.clone(), "[env]\ntests_bin-folder = \"tests/auto\"\n");
+    run_test(&working_path, &project_path, "integration/050.rs", true, "test auto::forest::catbus::catbus ... ok");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test auto::totoro::unit_test1 ... ok");
+    write_file(format!("{}/tests/auto/mei.rs", project_path), "#[test]\nfn mei(){\n\n}\n");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test auto::mei::mei ... ok");
+    remove_file(cargo_config.clone());
+    write_file(settings_path.clone(), "folder = \"tests/auto\"\n");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test auto::mei::mei ... ok");
+    write_file(format!("{}/tests/auto/forest/kiki.rs", project_path), "#[test]\nfn kiki(){\n\n}\n");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test auto::forest::kiki::kiki ... ok");
+
+    // V56 | Error : unit_tests_auto! without module tree.
+    remove_file(build_path);
+    write_file(manifest_path.clone(), manifest.as_str());
+    run_test(&working_path, &project_path, "integration/050.rs", false, "Module tree not generated! `tests_bin_build::generate()` should be called from `build.rs`.");
+    remove_file(settings_path.clone());
+
+    // V57 | unit_tests_mirror! links mirror tests files and warns on mismatches.
+    let ponyo_path = format!("{}/src/ponyo", project_path);
+    let mirror_path = format!("{}/tests/mirror", project_path);
+    write_file(format!("{}.rs", ponyo_path), "pub mod ham;\n\npub fn ham_count() -> u32 {\n    5\n}\n");
+    write_file(format!("{}/ham.rs", ponyo_path), "pub fn ham() -> u32 {\n    1\n}\n");
+    write_file(format!("{}/ponyo.rs", mirror_path), "#[test]\nfn ham_count(){\n    assert_eq!(crate::ponyo::ham_count(), 5);\n}\n");
+    write_file(format!("{}/ponyo/ham.rs", mirror_path), "#[test]\nfn ham(){\n    assert_eq!(crate::ponyo::ham::ham(), 1);\n}\n");
+    write_file(format!("{}/sosuke.rs", mirror_path), "");
+    write_file(cargo_config.clone(), "[env]\ntests_bin-folder = \"tests/mirror\"\n");
+    run_test(&working_path, &project_path, "integration/051.rs", true, "test mirror::ponyo::mod_::ham_count ... ok");
+    for expected in ["test mirror::ponyo::ham::ham ... ok", "Source file `src/main.rs` has no mirror tests file `tests/mirror/main.rs`!", 
+        "Mirror tests file `tests/mirror/sosuke.rs` has no source file `src/sosuke.rs`!"] {
+        assert_cmd!(&project_path, "cargo", ["test"], true, expected);
+    }
+
+    // V58 | Error : unit_tests_mirror! mismatches reported as errors.
+    write_file(cargo_config.clone(), "[env]\ntests_bin-folder = \"tests/mirror\"\ntests_bin-mirror-report = \"error\"\n");
+    run_test(&working_path, &project_path, "integration/051.rs", false, "Mirror tests file `tests/mirror/sosuke.rs` has no source file `src/sosuke.rs`!");
+    remove_file(cargo_config.clone());
+    remove_file(format!("{}.rs", ponyo_path));
+    for path in [ponyo_path, mirror_path] {
+        match std::fs::remove_dir_all(path) {
+            Ok(_) => {},
+            Err(err) => panic!("{:?}", err),    // Panic if we can't delete mirror directories.
+        }
+    }
+
+    // V59 | Macros work in inline modules and folders files can have inner attributes.
+    let phys_path = format!("{}/tests/unit/phys", project_path);
+    write_file(format!("{}/gravity.rs", phys_path), "//! Gravity tests.\n#![allow(dead_code)]\n\n#[test]\nfn gravity(){\n\n}\n");
+    run_test(&working_path, &project_path, "integration/052.rs", true, "test phys::gravity::gravity ... ok");
+    for expected in ["test inner::inner_phys::gravity::gravity ... ok", "test inner::tests_fn_ponyo::unit_test1 ... ok"] {
+        assert_cmd!(&project_path, "cargo", ["test"], true, expected);
+    }
+    match std::fs::remove_dir_all(phys_path) {
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't delete phys directory.
+    }
+
+    // V60 | Error : `=` before `attrs(...)` group.
+    run_test(&working_path, &project_path, "integration/053.rs", false, "Incorrect parameters!");
+
+    // V61 | Default `cfg(bench)` warns as unexpected until declared in `[lints.rust]`.
+    let unexpected_cfg = "unexpected `cfg` condition name: `bench`";
+    run_test(&working_path, &project_path, "integration/027.rs", true, unexpected_cfg);
+    match append_file(format!("{}/Cargo.toml", project_path), String::from("\n[lints.rust]\nunexpected_cfgs = { level = \"warn\", check-cfg = ['cfg(bench)'] }\n")) {
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't append Cargo.toml
+    }
+    let result = run_command(&project_path, "cargo", vec!["test"]);
+    assert!(result.0 && !result.1.contains(unexpected_cfg), "Expected no unexpected cfg warning but got {}!", result.1);
+
+    // V62 | Tests file with fixtures in module mode keeps inner attributes and nested modules.
+    let heat_path = format!("{}/tests/unit/heat", project_path);
+    write_file(format!("{}/heat.rs", heat_path), "//! Heat tests.\n#![allow(dead_code)]\nuse super::*;\nmod helper;\n\n#[test]\nfn heat_fixture(){\n    assert_eq!(heat(), helper::three());\n    assert!(fixture(\"sample.json\").exists());\n}\n");
+    write_file(format!("{}/helper.rs", heat_path), "pub fn three() -> u32 {\n    3\n}\n");
+    run_test(&working_path, &project_path, "integration/054.rs", true, "test tests_fn_heat::tests::heat_fixture ... ok");
+    match std::fs::remove_dir_all(heat_path) {
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't delete heat directory.
+    }
+
+    // V63 | Same module name in sibling inline modules has no hash suffix.
+    run_test(&working_path, &project_path, "integration/055.rs", true, "test a::tests_fn_add::unit_test1 ... ok");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test b::tests_fn_add::unit_test1 ... ok");
+
+    // V64 | Paths of macros in inline modules are relative to the folder implied by the modules.
+    let tools_path = format!("{}/src/tools", project_path);
+    write_file(format!("{}/README.md", tools_path), "");
+    run_test(&working_path, &project_path, "integration/056.rs", true, "test tools::tests_fn_add::unit_test1 ... ok");
+    assert_cmd!(&project_path, "cargo", ["test"], true, "test tools::tests_fn_decode::tests::decode_fixture ... ok");
+    match std::fs::remove_dir_all(tools_path) {
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't delete tools directory.
+    }
+
     // Clean integration test folders
     clean_integration_test(working_path, project_path);
 }
\ No newline at end of file
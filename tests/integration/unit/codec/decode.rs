@@ -0,0 +1,8 @@
+use super::*;
+
+#[test]
+fn decode_fixture(){
+    let data = std::fs::read_to_string(fixture("sample.json")).unwrap();
+    assert!(data.contains("ponyo"));
+    assert!(FIXTURES.ends_with("codec/decode/"));
+}
@@ -0,0 +1,4 @@
+#[test]
+fn unit_test1(){
+
+}
@@ -0,0 +1,4 @@
+#[test]
+fn add_works(){
+    assert_three(add(1, 2));
+}
@@ -47,7 +47,7 @@ fn performance_tests() {
         if i % REPORT_DIV == 0 {   // Reporting test loop
             println!("`Legacy` loop {} running...", i);
         }
-        legacy = legacy + get_run_time_micros(&project_path);
+        legacy += get_run_time_micros(&project_path);
     }
 
     // V4 | Display legacy performance time in ms.
@@ -62,7 +62,7 @@ fn performance_tests() {
         if i % REPORT_DIV == 0 {   // Reporting test loop
             println!("`tests_bin` loop {} running...", i);
         }
-        tb = tb + get_run_time_micros(&project_path);
+        tb += get_run_time_micros(&project_path);
     }
 
     // V7 | Display tests_bin performance time in ms.
@@ -80,7 +80,7 @@ fn performance_tests() {
  * Clean project, run cargo test and get time in microseconds.
  */
 #[inline(always)]
-pub fn get_run_time_micros(project_path : &String) -> i128 {
+pub fn get_run_time_micros(project_path : &str) -> i128 {
 
     // 1. Clean test project
     assert_cmd!(project_path, "cargo", ["clean" ], true, "");
@@ -9,6 +9,7 @@ mod performance;
 pub const CARGO_MANIFEST_DIR : &str = "CARGO_MANIFEST_DIR";             // Cargo manifest directory key
 pub const CARGO_PKG_VERSION : &str = "CARGO_PKG_VERSION";               // Cargo package version key
 pub const CARGO_PKG_NAME : &str = "CARGO_PKG_NAME";                     // Cargo package name key
+pub const SETTINGS_PKG_NAME : &str = "tests_bin_settings";              // Settings package `tests_bin` depends on
 
 /// Macro that run a command and assert result.
 /// Params : Working dir, command, arguments, success expected (true, false), message expected.
@@ -63,8 +64,8 @@ pub fn init_integration_test(test_name : &str) -> (String, String) {
     // 3. Create test project and assert if success.
     assert_cmd!(&working_path, "cargo", ["new", test_name ], true, "binary (application)");
     
-    // 4. Package `tests_bin` and allow-dirty
-    assert_cmd!(&working_path, "cargo", ["package", "--allow-dirty"], true, "Packaging");
+    // 4. Package `tests_bin` with `tests_bin_settings` it depends on and allow-dirty
+    assert_cmd!(&working_path, "cargo", ["package", "--allow-dirty", "-p", "tests_bin", "-p", SETTINGS_PKG_NAME], true, "Packaging");
 
     // 5. Copy package in project
     #[allow(unused_assignments)]
@@ -91,7 +92,9 @@ pub fn init_integration_test(test_name : &str) -> (String, String) {
     }
 
     // 6. Add dependency to new project Cargo.toml
-    let dependency = format!("{} = {{ path=\"{}\", version=\"{}\" }}", pkg_name, package_name, pkg_version);
+    //    Unpublished settings package is taken from the workspace, like `tests_bin_build` in build dependencies.
+    let dependency = format!("{} = {{ path=\"{}\", version=\"{}\" }}\n\n[patch.crates-io]\n{} = {{ path=\"{}/{}\" }}\n", 
+        pkg_name, package_name, pkg_version, SETTINGS_PKG_NAME, working_path, SETTINGS_PKG_NAME);
     match append_file(format!("{}/Cargo.toml", project_path), dependency){
         Ok(_) => {},
         Err(err) => panic!("{:?}", err),    // Panic if we can't append Cargo.toml
@@ -124,6 +127,39 @@ pub fn append_file(file_path : String, data : String) -> Result<bool, Error> {
 
 }
 
+/**
+ * Write data to a file, creating parent folders and replacing existing content.
+ */
+pub fn write_file(file_path : String, data : &str) {
+
+    if let Some(parent) = Path::new(&file_path).parent() {
+        match fs::create_dir_all(parent){
+            Ok(_) => {},
+            Err(err) => panic!("{:?}", err),    // Panic if we can't create parent folders
+        }
+    }
+
+    match fs::write(file_path, data){
+        Ok(_) => {},
+        Err(err) => panic!("{:?}", err),    // Panic if we can't write file
+    }
+
+}
+
+/**
+ * Remove a file if it exists.
+ */
+pub fn remove_file(file_path : String) {
+
+    if Path::new(&file_path).exists() {
+        match fs::remove_file(file_path){
+            Ok(_) => {},
+            Err(err) => panic!("{:?}", err),    // Panic if we can't remove file
+        }
+    }
+
+}
+
 /**
  * Clean project, copy test file to destination, run cargo test and compare result to expected.
  */
@@ -149,11 +185,38 @@ pub fn copy_file(src : String, dst : String){
     }
 }
 
+/// Run shell command without cargo env variables, with envs given, and return if success and output message as string
+pub fn run_command_outside_cargo(working_dir : &str, command : &str, args : Vec<&str>, envs : Vec<(&str, &str)>) -> (bool, String) {
+
+    let mut cmd = Command::new(command);
+    cmd.current_dir(Path::new(working_dir)).args(args).envs(envs);
+    for (key, _) in std::env::vars() {
+        if key.starts_with("CARGO") {
+            cmd.env_remove(key);
+        }
+    }
+
+    match cmd.output(){
+        Ok(output) => {
+            // Accumulate all output
+            let mut vec_out = output.stdout; 
+            vec_out.append(&mut output.stderr.clone());
+
+            match String::from_utf8(vec_out){
+                Ok(message) =>  (output.status.success(), message),
+                Err(err) => (output.status.success(), err.to_string()),
+            }
+        },
+        Err(err) => (false, err.to_string()),
+    }
+
+}
+
 /// Run shell command and return if success and output message as string
-pub fn run_command(working_dir : &String, command : &str, args : Vec<&str>) -> (bool, String) {
+pub fn run_command(working_dir : &str, command : &str, args : Vec<&str>) -> (bool, String) {
 
     // Set working directory first
-    match std::env::set_current_dir(Path::new(working_dir.as_str())){
+    match std::env::set_current_dir(Path::new(working_dir)){
         Ok(_) => {
             // Create command and match output
             match Command::new(command).args(args).output(){
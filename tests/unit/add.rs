@@ -0,0 +1,5 @@
+// Unit tests file linked by documentation examples.
+#[test]
+fn unit_test(){
+
+}
@@ -0,0 +1,173 @@
+//! Build script companion of [tests_bin](https://crates.io/crates/tests_bin) linking every unit tests file of a folder.
+//!
+//! The build script tells cargo to run it again when the tests bin folder changes, and writes its [`Builder`] settings
+//! in `OUT_DIR` for `unit_tests_auto!()`, which links a module tree mirroring the folder. New unit tests files are
+//! linked without touching the source.
+//!
+//! The module tree isn't written by the build script, since it runs once for all targets of the package while the `{target}`
+//! placeholder, module names and `#[path]` relative to the invoking file are only known when each target expands the macro.
+//!
+//! # Example
+//! `build.rs` with `tests_bin_build` in `[build-dependencies]` :
+//! ```ignore
+//! fn main() {
+//!     tests_bin_build::generate();
+//! }
+//! ```
+//! `lib.rs` or `main.rs` :
+//! ```ignore
+//! tests_bin::unit_tests_auto!();
+//! ```
+//! Files of `tests/unit` are linked in a module named after the folder, so that `tests/unit/physics/aabb.rs`
+//! becomes `unit::physics::aabb`.
+//!
+//! # Configuration
+//! The folder and cfg predicate are given by [`Builder`], or else read like tests_bin macros from env keys,
+//! `tests_bin.toml`, `[package.metadata.tests_bin]` and `[workspace.metadata.tests_bin]`. With the `{target}`
+//! placeholder, the folder containing every target folder is watched.
+//!
+//! # Targets
+//! Modules of `src/lib.rs`, `src/main.rs` and binaries of `src/bin` are followed from their sources, and unit tests
+//! files linked from two of them with literal paths or the path convention are reported as cargo warnings.
+
+use std::io::Write;
+use std::path::Path;
+
+use tests_bin_settings::{ProjectSettings, TARGET_PLACEHOLDER};
+
+use crate::targets::find_shared_files;
+
+mod targets;
+
+// Contants
+const AUTO_SETTINGS_FILE : &str = "tests_bin_auto.toml";            // Builder settings written in OUT_DIR, read by unit_tests_auto!()
+const TESTS_BIN_BASE_FOLDER : &str = "tests/unit";                  // Default tests bin base folder
+const TESTS_BIN_BASE_FOLDER_KEY : &str = "tests_bin-folder";        // Key used to fetch custom base folder
+const TESTS_BIN_CFG_KEY : &str = "tests_bin-cfg";                   // Key used to fetch custom default cfg predicate
+const FOLDER_KEY : &str = "folder";                                 // Key of folder in builder settings
+const CFG_KEY : &str = "cfg";                                       // Key of cfg predicate in builder settings
+const NAME_KEY : &str = "name";                                     // Key of root module name in builder settings
+const CARGO_MANIFEST_DIR : &str = "CARGO_MANIFEST_DIR";             // Cargo manifest directory key
+const OUT_DIR : &str = "OUT_DIR";                                   // Build script output directory key
+
+/// Prepare the module tree of the tests bin folder, linked by `unit_tests_auto!()`, with default settings.
+///
+/// # Panic(s)
+/// Panics if the builder settings can't be written in `OUT_DIR`.
+pub fn generate() {
+
+    if let Err(err) = Builder::new().generate() {
+        panic!("tests_bin_build can't write builder settings : {}", err);
+    }
+
+}
+
+/// Builder of the module tree linking every unit tests file of a folder.
+#[derive(Default)]
+pub struct Builder {
+    folder : Option<String>,
+    cfg : Option<String>,
+    name : Option<String>,
+}
+
+impl Builder {
+
+    /// Create a builder using tests_bin settings, or defaults.
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Set the tests bin folder, relative to `Cargo.toml` folder.
+    pub fn folder(mut self, folder : &str) -> Builder {
+        self.folder = Some(folder.to_string());
+        self
+    }
+
+    /// Set the cfg predicate of the root module, like `all(test, feature = "slow")`.
+    pub fn cfg(mut self, cfg : &str) -> Builder {
+        self.cfg = Some(cfg.to_string());
+        self
+    }
+
+    /// Set the root module name, named after the folder by default.
+    pub fn name(mut self, name : &str) -> Builder {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Write builder settings in `OUT_DIR` and tell cargo to run the build script again when the folder or settings change.
+    ///
+    /// Missing folders, settings that can't be read and unit tests files linked from many targets are reported as cargo warnings.
+    ///
+    /// Error(s)
+    /// Returns an error if `OUT_DIR` isn't set or the builder settings can't be written.
+    pub fn generate(self) -> std::io::Result<()> {
+
+        println!("cargo:rerun-if-env-changed={}", TESTS_BIN_BASE_FOLDER_KEY);
+        println!("cargo:rerun-if-env-changed={}", TESTS_BIN_CFG_KEY);
+
+        let out_dir = std::env::var(OUT_DIR).map_err(|_| std::io::Error::new(std::io::ErrorKind::NotFound, "Env variable `OUT_DIR` not set!"))?;
+        let manifest_dir = std::env::var(CARGO_MANIFEST_DIR).unwrap_or_default();
+        let settings = ProjectSettings::read(Path::new(&manifest_dir));
+
+        // Missing settings files are never watched, since cargo would always run the build script again
+        for file in settings.files().filter(|file| file.is_file()) {
+            println!("cargo:rerun-if-changed={}", file.display());
+        }
+        if let Some((file, line)) = settings.error() {
+            println!("cargo:warning=Unsupported settings `{}` in `{}`!", line, file.display());
+        }
+
+        // Folder is watched even if missing, so creating it runs the build script again. Targets folders are only
+        // known when compiling each target, so their parent folder is watched.
+        let base_folder = self.folder.clone().or_else(|| settings.get(TESTS_BIN_BASE_FOLDER_KEY, None)).unwrap_or(String::from(TESTS_BIN_BASE_FOLDER));
+        let folder = match base_folder.split_once(TARGET_PLACEHOLDER) {
+            Some((parent, _)) => Path::new(&manifest_dir).join(parent),
+            None => Path::new(&manifest_dir).join(&base_folder),
+        };
+        println!("cargo:rerun-if-changed={}", folder.display());
+
+        if !folder.is_dir() {
+            println!("cargo:warning=Tests folder `{}` not found!", base_folder);
+        }
+
+        // Targets are compiled by separate processes, so files linked from many targets are found from their sources
+        let (shared, sources) = find_shared_files(Path::new(&manifest_dir), &settings);
+        for source in sources {
+            println!("cargo:rerun-if-changed={}", source.display());
+        }
+        for (file, targets) in shared {
+            println!("cargo:warning=Tests file `{}` is linked from targets `{}`! Use the `{{target}}` placeholder in tests bin folder to separate targets tests.", 
+                file, targets.join("`, `"));
+        }
+
+        std::fs::File::create(Path::new(&out_dir).join(AUTO_SETTINGS_FILE))?.write_all(self.to_settings().as_bytes())
+
+    }
+
+    /// Get settings given to builder as `key = "value"` lines, other settings are read again by `unit_tests_auto!()`.
+    fn to_settings(&self) -> String {
+
+        [(FOLDER_KEY, &self.folder), (CFG_KEY, &self.cfg), (NAME_KEY, &self.name)].into_iter()
+            .filter_map(|(key, value)| Some(format!("{} = \"{}\"\n", key, value.as_ref()?.replace('\\', "\\\\").replace('"', "\\\""))))
+            .collect()
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Builder;
+
+    /// Test builder settings are written as TOML values, without settings not given
+    #[test]
+    fn builder_to_settings() {
+
+        assert_eq!(Builder::new().to_settings(), "", "Settings not given should not be written!");
+        assert_eq!(Builder::new().folder("tests/auto").cfg("all(test, feature = \"slow\")").to_settings(), 
+            "folder = \"tests/auto\"\ncfg = \"all(test, feature = \\\"slow\\\")\"\n", "Quotes should be escaped!");
+
+    }
+
+}
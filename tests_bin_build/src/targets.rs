@@ -0,0 +1,448 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use tests_bin_settings::{ProjectSettings, CARGO_FILE, parse_table, relative_path};
+
+// Contants
+const CARGO_PKG_NAME : &str = "CARGO_PKG_NAME";                     // Cargo package name key
+const TESTS_BIN_BASE_FOLDER : &str = "tests/unit";                  // Default tests bin base folder
+const TESTS_BIN_BASE_FOLDER_KEY : &str = "tests_bin-folder";        // Key used to fetch custom base folder
+const TESTS_BIN_CONVENTION : &str = "{ident}.rs";                   // Default path convention of #[unit_tests] without path
+const TESTS_BIN_CONVENTION_KEY : &str = "tests_bin-convention";     // Key used to fetch custom path convention
+const CONVENTION_IDENT : &str = "{ident}";                          // Convention placeholder replaced by item identifier
+const CONVENTION_KIND : &str = "{kind}";                            // Convention placeholder replaced by item kind
+const LIB_TABLE : &str = "lib";                                     // Table of library target in Cargo.toml
+const LIB_FILE : &str = "src/lib.rs";                               // Default library target root
+const MAIN_FILE : &str = "src/main.rs";                             // Default binary target root
+const BIN_FOLDER : &str = "src/bin";                                // Folder of other binary targets
+const MAIN_STEM : &str = "main";                                    // Stem of binaries roots in their own folder
+const UNIT_TESTS_MACRO : &str = "unit__tests";                      // Macro linking unit tests without item
+const UNIT_TESTS_ATTRIBUTE : &str = "unit_tests";                   // Attribute linking unit tests of an item
+const UNIT_TEST_FILE_ATTRIBUTE : &str = "unit_test_file";           // Helper attribute linking unit tests of a method
+const PATH_KEY : &str = "path";                                     // Key of path parameter, and of `#[path]` attribute
+const DIR_KEY : &str = "dir";                                       // Key of folder linking parameter
+const ROOT_KEY : &str = "root";                                     // Key of named tests root parameter
+const ITEM_KINDS : [&str; 9] = ["fn", "struct", "enum", "union", "trait", "type", "const", "static", "mod"];   // Items kinds named by convention
+const GLOB_CHARACTERS : [char; 2] = ['*', '?'];                     // Characters that make a path a glob pattern
+
+/// Token of source code, comments and characters literals are skipped.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Punct(char),
+}
+
+/// Unit tests path linked by a macro, relative to the tests bin folder.
+#[derive(Debug, Clone, PartialEq)]
+enum Link {
+    /// File given by path.
+    File(String),
+    /// Every file of a folder given with `dir`.
+    Folder(String),
+    /// File derived from item kind and identifier with the path convention.
+    Convention(String, String),
+}
+
+/// Module file declared with `mod name;`, with the inline modules enclosing it and its `#[path]`.
+#[derive(Debug, Clone, PartialEq)]
+struct ModuleDeclaration {
+    inline : Vec<String>,
+    name : String,
+    path : Option<String>,
+}
+
+/// Get unit tests files linked from two targets or more of the package, with the root files of their targets, both
+/// relative to the package folder, and the source files read.
+///
+/// Targets are told apart by root file since the library and `src/main.rs` have the same name by default.
+///
+/// Targets are `src/lib.rs`, `src/main.rs` and binaries of `src/bin`. Their module trees are followed from `mod name;`
+/// declarations, and files are found from literal paths of `unit__tests!` and `#[unit_tests]`, or from the path convention.
+pub(crate) fn find_shared_files(manifest_dir: &Path, settings: &ProjectSettings) -> (Vec<(String, Vec<String>)>, Vec<PathBuf>) {
+
+    let mut linked : BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    let mut sources : BTreeSet<PathBuf> = BTreeSet::new();
+
+    for (target, root) in find_targets(manifest_dir) {
+        let folder = manifest_dir.join(settings.get(TESTS_BIN_BASE_FOLDER_KEY, Some(&target)).unwrap_or(String::from(TESTS_BIN_BASE_FOLDER)));
+        let convention = settings.get(TESTS_BIN_CONVENTION_KEY, Some(&target)).unwrap_or(String::from(TESTS_BIN_CONVENTION));
+
+        let mut links : Vec<Link> = Vec::new();
+        let mut visited : BTreeSet<PathBuf> = BTreeSet::new();
+        scan_module_tree(&root, true, &mut links, &mut visited);
+        sources.extend(visited);
+
+        let mut files : BTreeSet<PathBuf> = BTreeSet::new();
+        for link in links {
+            match link {
+                Link::File(path) => { files.insert(folder.join(path)); },
+                Link::Convention(kind, ident) => { files.insert(folder.join(convention.replace(CONVENTION_IDENT, &ident).replace(CONVENTION_KIND, &kind))); },
+                Link::Folder(path) => list_files(&folder.join(path), &mut files),
+            }
+        }
+
+        let root = relative_path(manifest_dir, &root).unwrap_or(root.display().to_string());
+        for file in files.into_iter().filter_map(|file| file.canonicalize().ok()) {
+            linked.entry(file).or_default().push(root.clone());
+        }
+    }
+
+    let shared = linked.into_iter()
+        .filter(|(_, targets)| targets.len() > 1)
+        .map(|(file, targets)| (relative_path(manifest_dir, &file).unwrap_or(file.display().to_string()), targets))
+        .collect();
+
+    (shared, sources.into_iter().collect())
+
+}
+
+/// Get name and root file of targets of the package, the crate name for the library and binary names for binaries,
+/// like the `{target}` placeholder of tests_bin macros.
+fn find_targets(manifest_dir: &Path) -> Vec<(String, PathBuf)> {
+
+    let package = std::env::var(CARGO_PKG_NAME).unwrap_or_default();
+    let lib = std::fs::read_to_string(manifest_dir.join(CARGO_FILE)).ok()
+        .and_then(|content| parse_table(&content, LIB_TABLE).ok())
+        .unwrap_or_default();
+
+    let mut targets : Vec<(String, PathBuf)> = vec![
+        (lib.get("name").cloned().unwrap_or(package.replace('-', "_")), manifest_dir.join(lib.get(PATH_KEY).map(String::as_str).unwrap_or(LIB_FILE))),
+        (package, manifest_dir.join(MAIN_FILE)),
+    ];
+
+    if let Ok(entries) = std::fs::read_dir(manifest_dir.join(BIN_FOLDER)) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            match path.is_dir() {
+                true => targets.push((path.file_name().unwrap_or_default().to_string_lossy().to_string(), path.join(MAIN_STEM).with_extension("rs"))),
+                false if path.extension().is_some_and(|extension| extension == "rs") =>
+                    targets.push((path.file_stem().unwrap_or_default().to_string_lossy().to_string(), path)),
+                false => {},
+            }
+        }
+    }
+
+    targets.retain(|(_, root)| root.is_file());
+    targets.sort_by(|(_, a), (_, b)| a.cmp(b));
+    targets
+
+}
+
+/// Accumulate links of source file and of its sub modules files in links, with the files visited.
+fn scan_module_tree(file: &Path, mod_rs: bool, links: &mut Vec<Link>, visited: &mut BTreeSet<PathBuf>) {
+
+    let Ok(source) = std::fs::read_to_string(file) else {
+        return;
+    };
+    if !visited.insert(file.to_path_buf()) {
+        return;
+    }
+
+    let (declarations, file_links) = scan_source(&source);
+    links.extend(file_links);
+
+    let (Some(parent), Some(stem)) = (file.parent(), file.file_stem()) else {
+        return;
+    };
+
+    for declaration in declarations {
+        // Sub modules folder, next to `mod.rs` files, crate roots and `#[path]` files, else named after the file.
+        // `#[path]` outside of inline modules is always next to the file.
+        let mut folder = parent.to_path_buf();
+        let next_to_file = mod_rs || (declaration.path.is_some() && declaration.inline.is_empty());
+        if !next_to_file {
+            folder.push(stem);
+        }
+        folder.extend(declaration.inline.iter());
+
+        match declaration.path {
+            // Files of `#[path]` have their sub modules next to them
+            Some(path) => scan_module_tree(&folder.join(path), true, links, visited),
+            None => {
+                let child = folder.join(&declaration.name).with_extension("rs");
+                match child.is_file() {
+                    true => scan_module_tree(&child, false, links, visited),
+                    false => scan_module_tree(&folder.join(&declaration.name).join("mod.rs"), true, links, visited),
+                }
+            },
+        }
+    }
+
+}
+
+/// Get module files declarations and unit tests links of source code.
+///
+/// Paths built with macros, glob patterns and named roots are ignored.
+fn scan_source(source: &str) -> (Vec<ModuleDeclaration>, Vec<Link>) {
+
+    let tokens = tokenize(source);
+    let mut declarations : Vec<ModuleDeclaration> = Vec::new();
+    let mut links : Vec<Link> = Vec::new();
+    let mut scopes : Vec<Option<String>> = Vec::new();     // Braces opened, with the name of inline modules
+    let mut path : Option<String> = None;                   // `#[path]` of next module
+
+    let ident = |i: usize| match tokens.get(i) { Some(Token::Ident(ident)) => Some(ident.as_str()), _ => None };
+    let punct = |i: usize, c: char| tokens.get(i) == Some(&Token::Punct(c));
+
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            // `#[path = "..."]`
+            Token::Punct('#') if punct(i + 1, '[') && ident(i + 2) == Some(PATH_KEY) && punct(i + 3, '=') && punct(i + 5, ']') => {
+                if let Some(Token::Str(value)) = tokens.get(i + 4) {
+                    path = Some(value.clone());
+                }
+                i += 5;
+            },
+
+            // `#[unit_tests]`, `#[unit_tests(...)]`, `#[tests_bin::unit_tests(...)]` or `#[unit_test_file(...)]`
+            Token::Punct('#') if punct(i + 1, '[') => {
+                let mut end = i + 2;
+                while ident(end).is_some() && punct(end + 1, ':') && punct(end + 2, ':') { end += 3; }
+                if let Some(attribute @ (UNIT_TESTS_ATTRIBUTE | UNIT_TEST_FILE_ATTRIBUTE)) = ident(end) {
+                    let (arguments, close) = match punct(end + 1, '(') {
+                        true => { let (arguments, close) = get_group(&tokens, end + 1); (arguments, close + 1) },
+                        false => (Vec::new(), end + 1),
+                    };
+                    match parse_link(&arguments) {
+                        Some(Some(link)) => links.push(link),
+                        Some(None) if attribute == UNIT_TESTS_ATTRIBUTE => 
+                            links.extend(get_item_signature(&tokens, close + 1).map(|(kind, ident)| Link::Convention(kind, ident))),
+                        _ => {},
+                    }
+                    i = close;
+                }
+            },
+
+            // `unit__tests!(...)`
+            Token::Ident(name) if name == UNIT_TESTS_MACRO && punct(i + 1, '!') => {
+                let (arguments, close) = get_group(&tokens, i + 2);
+                links.extend(parse_link(&arguments).flatten());
+                i = close;
+            },
+
+            // `mod name;` or `mod name {`
+            Token::Ident(keyword) if keyword == "mod" && ident(i + 1).is_some() => {
+                let name = ident(i + 1).unwrap_or_default().to_string();
+                match punct(i + 2, ';') {
+                    true => declarations.push(ModuleDeclaration { inline: scopes.iter().flatten().cloned().collect(), name, path: path.take() }),
+                    false if punct(i + 2, '{') => scopes.push(Some(name)),
+                    false => {},
+                }
+                path = None;
+                i += 2;
+            },
+
+            Token::Punct('{') => { scopes.push(None); path = None; },
+            Token::Punct('}') => { scopes.pop(); path = None; },
+            Token::Punct(';') => path = None,
+            _ => {},
+        }
+        i += 1;
+    }
+
+    (declarations, links)
+
+}
+
+/// Get link of macro arguments, `Some(None)` if path is derived from the item, or `None` if it can't be found.
+fn parse_link(arguments: &[Token]) -> Option<Option<Link>> {
+
+    let mut link : Option<Link> = None;
+
+    for argument in arguments.split(|token| *token == Token::Punct(',')) {
+        match argument {
+            [Token::Str(path)] if link.is_none() => link = Some(Link::File(path.clone())),
+            [Token::Ident(key), Token::Punct('='), Token::Str(path)] if key == PATH_KEY => link = Some(Link::File(path.clone())),
+            [Token::Ident(key), Token::Punct('='), Token::Str(path)] if key == DIR_KEY => link = Some(Link::Folder(path.clone())),
+            [Token::Ident(key), ..] if key == ROOT_KEY => return None,
+            [Token::Ident(_), Token::Punct('='), Token::Str(_)] | [Token::Str(_)] | [Token::Ident(_), Token::Punct('('), ..] | [] => {},
+            _ => return None,     // Paths built with macros
+        }
+    }
+
+    match link {
+        Some(Link::File(path) | Link::Folder(path)) if path.contains(GLOB_CHARACTERS) => None,
+        link => Some(link),
+    }
+
+}
+
+/// Get kind and identifier of item starting at index, after its attributes and visibility.
+fn get_item_signature(tokens: &[Token], mut i: usize) -> Option<(String, String)> {
+
+    loop {
+        match tokens.get(i)? {
+            Token::Punct('#') => i = get_group(tokens, i + 1).1 + 1,
+            Token::Ident(ident) if ident == "pub" && tokens.get(i + 1) == Some(&Token::Punct('(')) => i = get_group(tokens, i + 1).1 + 1,
+            Token::Ident(ident) if ITEM_KINDS.contains(&ident.as_str()) && tokens.get(i + 1) != Some(&Token::Ident(String::from("fn"))) => match tokens.get(i + 1)? {
+                Token::Ident(name) => return Some((ident.clone(), name.clone())),
+                _ => return None,
+            },
+            Token::Ident(_) | Token::Str(_) => i += 1,     // Visibility and qualifiers like `unsafe` or `extern "C"`
+            Token::Punct(_) => return None,
+        }
+    }
+
+}
+
+/// Get tokens of group opened at index, like `(...)` or `[...]`, with the index of its closing token.
+fn get_group(tokens: &[Token], open: usize) -> (Vec<Token>, usize) {
+
+    let mut depth = 0;
+
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::Punct('(' | '[' | '{') => depth += 1,
+            Token::Punct(')' | ']' | '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return (tokens[open + 1..i].to_vec(), i);
+                }
+            },
+            _ => {},
+        }
+        if depth == 0 {
+            break;
+        }
+    }
+
+    (Vec::new(), open)
+
+}
+
+/// Split source code in identifiers, strings and punctuations, skipping comments, characters and numbers.
+fn tokenize(source: &str) -> Vec<Token> {
+
+    let source : Vec<char> = source.chars().collect();
+    let mut tokens : Vec<Token> = Vec::new();
+    let mut i = 0;
+
+    while i < source.len() {
+        match (source[i], source.get(i + 1).copied()) {
+            // Line comment
+            ('/', Some('/')) => while i < source.len() && source[i] != '\n' { i += 1; },
+
+            // Block comment, which can be nested
+            ('/', Some('*')) => {
+                let mut comments = 0;
+                while i < source.len() {
+                    match (source[i], source.get(i + 1).copied()) {
+                        ('/', Some('*')) => { comments += 1; i += 2; },
+                        ('*', Some('/')) => { comments -= 1; i += 2; if comments == 0 { break; } },
+                        _ => i += 1,
+                    }
+                }
+                continue;
+            },
+
+            // String, with escaped quotes and backslashes decoded
+            ('"', _) => {
+                let mut value = String::new();
+                i += 1;
+                while i < source.len() && source[i] != '"' {
+                    if source[i] == '\\' {
+                        i += 1;
+                    }
+                    value.extend(source.get(i));
+                    i += 1;
+                }
+                tokens.push(Token::Str(value));
+            },
+
+            // Character literal, or lifetime which is kept as an identifier
+            ('\'', Some('\\')) => {
+                i += 3;
+                while i < source.len() && source[i] != '\'' { i += 1; }
+            },
+            ('\'', Some(_)) if source.get(i + 2) == Some(&'\'') => i += 2,
+
+            // Identifier, which can start a raw string like `r#"..."#`
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < source.len() && (source[i].is_alphanumeric() || source[i] == '_') { i += 1; }
+                let word : String = source[start..i].iter().collect();
+                let hashes = source[i..].iter().take_while(|c| **c == '#').count();
+                if matches!(word.as_str(), "r" | "br" | "cr") && source.get(i + hashes) == Some(&'"') {
+                    i += hashes + 1;
+                    let start = i;
+                    while i < source.len() && !(source[i] == '"' && source[i + 1..].iter().take(hashes).filter(|c| **c == '#').count() == hashes) { i += 1; }
+                    tokens.push(Token::Str(source[start..i.min(source.len())].iter().collect()));
+                    i += hashes + 1;
+                } else {
+                    tokens.push(Token::Ident(word));
+                }
+                continue;
+            },
+
+            // Numbers
+            (c, _) if c.is_ascii_digit() => while i + 1 < source.len() && (source[i + 1].is_alphanumeric() || source[i + 1] == '_') { i += 1; },
+
+            (c, _) if c.is_whitespace() => {},
+            (c, _) => tokens.push(Token::Punct(c)),
+        }
+        i += 1;
+    }
+
+    tokens
+
+}
+
+/// Accumulate files of folder and its sub folders in files.
+fn list_files(folder: &Path, files: &mut BTreeSet<PathBuf>) {
+
+    if let Ok(entries) = std::fs::read_dir(folder) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            match path.is_dir() {
+                true => list_files(&path, files),
+                false => { files.insert(path); },
+            }
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::targets::{scan_source, Link, ModuleDeclaration};
+
+    /// Test links of macros and module declarations are found, ignoring comments and paths that can't be resolved
+    #[test]
+    fn scan_source_links() {
+
+        let source = r#"
+            // unit__tests!("comment.rs");
+            use tests_bin::{ unit__tests, unit_tests };
+
+            unit__tests!("ponyo.rs", "ham");
+            tests_bin::unit__tests!(name = "named", path = r"sosuke.rs", attrs(allow(dead_code)));
+            unit__tests!(dir = "physics");
+            unit__tests!(concat!("lisa", ".rs"));
+            unit__tests!("math/*.rs");
+
+            #[tests_bin::unit_tests(root = "golden", path = "howl.rs")]
+            fn howl() {}
+
+            #[unit_tests]
+            #[inline]
+            pub(crate) fn kiki() {}
+
+            #[path = "tools/ham.rs"]
+            mod ham;
+
+            mod inner {
+                pub mod totoro;
+            }
+        "#;
+
+        let (declarations, links) = scan_source(source);
+        assert_eq!(links, [Link::File(String::from("ponyo.rs")), Link::File(String::from("sosuke.rs")), Link::Folder(String::from("physics")),
+            Link::Convention(String::from("fn"), String::from("kiki"))], "Expected links of literal paths and convention only!");
+        assert_eq!(declarations, [
+            ModuleDeclaration { inline: vec![], name: String::from("ham"), path: Some(String::from("tools/ham.rs")) },
+            ModuleDeclaration { inline: vec![String::from("inner")], name: String::from("totoro"), path: None },
+        ], "Expected module files declarations with `#[path]` and enclosing inline modules!");
+
+    }
+
+}
@@ -0,0 +1,615 @@
+//! Settings resolution shared by [tests_bin](https://crates.io/crates/tests_bin) macros and the `tests_bin_build` build
+//! script, so that both read the same tests bin folder.
+//!
+//! Settings are read, in order of precedence, from :
+//! 1. Env keys like `tests_bin-folder`, usually set in `.cargo/config.toml` `[env]`.
+//! 2. `tests_bin.toml` next to `Cargo.toml`, with keys without `tests_bin-` prefix like `folder = "tests/unit"`.
+//! 3. `[package.metadata.tests_bin]` table of `Cargo.toml`, with the same keys as `tests_bin.toml`.
+//! 4. `[workspace.metadata.tests_bin]` table of the workspace root `Cargo.toml`, shared by all members.
+//!
+//! Values can use the `{workspace}`, `{crate}` and `{target}` placeholders.
+//!
+//! This crate is internal to tests_bin and `tests_bin_build`, its API isn't stable and may change in any release.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf, Component};
+use std::time::SystemTime;
+
+// Contants
+pub const SETTINGS_FILE : &str = "tests_bin.toml";                  // Settings file next to Cargo.toml
+pub const CARGO_FILE : &str = "Cargo.toml";                         // Cargo manifest file
+pub const TARGET_PLACEHOLDER : &str = "{target}";                   // Placeholder replaced by name of target being compiled
+const SETTINGS_KEY_PREFIX : &str = "tests_bin-";                    // Prefix of env keys, removed in settings files
+const CARGO_METADATA_TABLE : &str = "package.metadata.tests_bin";   // Table of settings in Cargo.toml
+const WORKSPACE_TABLE : &str = "workspace";                         // Table making a Cargo.toml a workspace root
+const WORKSPACE_MEMBERS_KEY : &str = "members";                     // Key of workspace members paths, which can be glob patterns
+const WORKSPACE_EXCLUDE_KEY : &str = "exclude";                     // Key of paths excluded from workspace
+const PACKAGE_TABLE : &str = "package";                             // Table of package in Cargo.toml
+const PACKAGE_WORKSPACE_KEY : &str = "workspace";                   // Key of package pointing to its workspace root
+const DEPENDENCIES_TABLE : &str = "dependencies";                   // Suffix of dependencies tables, like `dev-dependencies`
+const DEPENDENCY_PATH_KEY : &str = "path";                          // Key of path dependencies, which are workspace members
+const WORKSPACE_METADATA_TABLE : &str = "workspace.metadata.tests_bin"; // Table of workspace settings in root Cargo.toml
+const CARGO_PKG_NAME : &str = "CARGO_PKG_NAME";                     // Cargo package name key
+const WORKSPACE_PLACEHOLDER : &str = "{workspace}";                 // Placeholder replaced by workspace root folder
+const CRATE_PLACEHOLDER : &str = "{crate}";                         // Placeholder replaced by package name
+const TOML_COMMENT : char = '#';                                    // Start of TOML comments
+const TOML_ASSIGN : char = '=';                                     // TOML key and value assignment
+const TOML_DOTTED_KEY : char = '.';                                 // Separator of TOML dotted keys and tables names
+const TOML_MULTILINE_STRINGS : [&str; 2] = ["\"\"\"", "'''"];       // Start of TOML multi-line strings
+
+/// Settings tables of a project folder, with the files read to get them.
+#[derive(Clone, Default)]
+pub struct ProjectSettings {
+    manifest_dir : PathBuf,                             // Project folder, placeholders are relative to
+    tables : Vec<BTreeMap<String, String>>,             // Settings file, package and workspace tables, in order of precedence
+    workspace_root : Option<PathBuf>,                   // Root folder of the workspace the package is a member of
+    files : Vec<(PathBuf, Option<SystemTime>)>,         // Files read or looked for, with their modification time
+    error : Option<(PathBuf, String)>,                  // First line with unsupported syntax, with its file
+}
+
+impl ProjectSettings {
+
+    /// Read settings of `tests_bin.toml`, `[package.metadata.tests_bin]` and `[workspace.metadata.tests_bin]` of project folder.
+    pub fn read(manifest_dir: &Path) -> ProjectSettings {
+
+        let mut settings = ProjectSettings { manifest_dir: manifest_dir.to_path_buf(), ..Default::default() };
+
+        let workspace_root = settings.find_workspace_root(manifest_dir);
+
+        let mut sources = vec![(manifest_dir.join(SETTINGS_FILE), ""), (manifest_dir.join(CARGO_FILE), CARGO_METADATA_TABLE)];
+        if let Some(workspace_root) = &workspace_root {
+            sources.push((workspace_root.join(CARGO_FILE), WORKSPACE_METADATA_TABLE));
+        }
+
+        for (file, table) in sources {
+            let content = settings.read_file(&file).unwrap_or_default();
+            match parse_table(&content, table) {
+                Ok(values) => settings.tables.push(values),
+                Err(line) => {
+                    settings.error.get_or_insert((file, line));
+                    settings.tables.push(BTreeMap::new());
+                },
+            }
+        }
+
+        settings.workspace_root = workspace_root;
+        settings
+
+    }
+
+    /// Get a setting value from env key like `tests_bin-folder`, or else from the key without `tests_bin-` prefix
+    /// in settings tables.
+    ///
+    /// Placeholders `{workspace}` and `{crate}` are replaced by workspace root folder, relative to project folder, and
+    /// package name. `{target}` is replaced by target name, or kept if not given, like in build scripts.
+    ///
+    /// Returns `None` if setting isn't set, so the default value is used.
+    pub fn get(&self, env_key: &str, target: Option<&str>) -> Option<String> {
+
+        let value = match std::env::var(env_key) {
+            Ok(value) => value,
+            Err(_) => {
+                let key = env_key.strip_prefix(SETTINGS_KEY_PREFIX).unwrap_or(env_key);
+                self.tables.iter().find_map(|table| table.get(key).cloned())?
+            },
+        };
+
+        Some(self.expand_placeholders(&value, target))
+
+    }
+
+    /// Get files read or looked for, so that changing them reads settings again.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+
+        self.files.iter().map(|(file, _)| file.as_path())
+
+    }
+
+    /// Get the first line of settings files using a syntax that can't be read, like inline tables or dotted keys,
+    /// with its file.
+    pub fn error(&self) -> Option<(&Path, &str)> {
+
+        self.error.as_ref().map(|(file, line)| (file.as_path(), line.as_str()))
+
+    }
+
+    /// Returns true if no file read was created, changed or removed since settings were read.
+    pub fn is_current(&self) -> bool {
+
+        self.files.iter().all(|(file, modified)| get_modified_time(file) == *modified)
+
+    }
+
+    /// Find root folder of the workspace of the package, like cargo :
+    /// 1. The package folder if its `Cargo.toml` has `[workspace]`.
+    /// 2. The folder given by `package.workspace`.
+    /// 3. The nearest parent folder with `[workspace]` that doesn't exclude the package. The package must be listed in
+    ///    its `members`, or be one of its path dependencies, else it isn't in a workspace.
+    fn find_workspace_root(&mut self, manifest_dir: &Path) -> Option<PathBuf> {
+
+        let content = self.read_file(&manifest_dir.join(CARGO_FILE)).unwrap_or_default();
+        if has_table(&content, WORKSPACE_TABLE) {
+            return Some(manifest_dir.to_path_buf());
+        }
+
+        if let Some(root) = find_value(&content, PACKAGE_TABLE, PACKAGE_WORKSPACE_KEY).and_then(|value| parse_string_value(&value)) {
+            return Some(manifest_dir.join(root));
+        }
+
+        for folder in manifest_dir.ancestors().skip(1) {
+            let Some(content) = self.read_file(&folder.join(CARGO_FILE)).filter(|content| has_table(content, WORKSPACE_TABLE)) else {
+                continue;
+            };
+
+            match relative_path(folder, manifest_dir).and_then(|member| is_workspace_member(&content, &member)) {
+                Some(true) => return Some(folder.to_path_buf()),
+                Some(false) => return None,
+                None => {},     // Excluded, parent workspaces are looked for
+            }
+        }
+
+        None
+
+    }
+
+    /// Read content of file and keep its modification time, `None` if it can't be read.
+    fn read_file(&mut self, file: &Path) -> Option<String> {
+
+        if !self.files.iter().any(|(read, _)| read == file) {
+            self.files.push((file.to_path_buf(), get_modified_time(file)));
+        }
+
+        std::fs::read_to_string(file).ok()
+
+    }
+
+    /// Replace `{workspace}` by workspace root folder relative to project folder, `.` if it's the same or unknown,
+    /// `{crate}` by package name and `{target}` by target name if given.
+    fn expand_placeholders(&self, value: &str, target: Option<&str>) -> String {
+
+        let mut value = value.to_string();
+
+        if value.contains(WORKSPACE_PLACEHOLDER) {
+            let workspace = self.workspace_root.as_ref()
+                .and_then(|workspace_root| relative_path(&self.manifest_dir, workspace_root))
+                .filter(|workspace| !workspace.is_empty())
+                .unwrap_or(String::from("."));
+            value = value.replace(WORKSPACE_PLACEHOLDER, &workspace);
+        }
+
+        if value.contains(CRATE_PLACEHOLDER) {
+            value = value.replace(CRATE_PLACEHOLDER, &std::env::var(CARGO_PKG_NAME).unwrap_or_default());
+        }
+
+        if let Some(target) = target {
+            value = value.replace(TARGET_PLACEHOLDER, target);
+        }
+
+        value
+
+    }
+
+}
+
+/// Get modification time of file, `None` if it doesn't exist.
+fn get_modified_time(file: &Path) -> Option<SystemTime> {
+
+    std::fs::metadata(file).and_then(|metadata| metadata.modified()).ok()
+
+}
+
+/// Parse string values of a table from TOML content, like `[package.metadata.tests_bin]`. Root table is `""`.
+///
+/// Only `key = "value"` and `key = 'value'` pairs are read, other values are ignored.
+/// 
+/// Error(s)
+/// Returns the first line setting the table with another syntax, like an inline table `tests_bin = { ... }`, dotted keys
+/// `tests_bin.folder = "..."` or multi-line strings, so that settings aren't silently ignored.
+pub fn parse_table(content: &str, table: &str) -> Result<BTreeMap<String, String>, String> {
+
+    let mut values : BTreeMap<String, String> = BTreeMap::new();
+    let mut current = String::new();        // Current table name
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        // Table header `[name]`, arrays of tables `[[name]]` are never read
+        if let Some(name) = parse_header(line) {
+            current = name;
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(TOML_ASSIGN).filter(|_| !line.starts_with(TOML_COMMENT)) else {
+            continue;
+        };
+
+        // Quoted keys are a single key, even with dots
+        let key = key.trim();
+        let key : Vec<&str> = match key.starts_with(['"', '\'']) && key.ends_with(['"', '\'']) {
+            true => vec![key.trim_matches('"').trim_matches('\'')],
+            false => key.split(TOML_DOTTED_KEY).map(|part| part.trim().trim_matches('"').trim_matches('\'')).collect(),
+        };
+
+        if current == table {
+            let value = value.trim();
+            if key.len() > 1 || TOML_MULTILINE_STRINGS.iter().any(|start| value.starts_with(start)) {
+                return Err(line.to_string());
+            }
+            if let Some(value) = parse_string_value(value) {
+                values.insert(key[0].to_string(), value);
+            }
+        } else if !table.is_empty() {
+            // Key of a parent table naming the table, like `tests_bin = { ... }` in `[package.metadata]`
+            let path = current.split(TOML_DOTTED_KEY).filter(|part| !part.is_empty()).chain(key).collect::<Vec<&str>>().join(".");
+            if path == table || path.starts_with(format!("{}{}", table, TOML_DOTTED_KEY).as_str()) {
+                return Err(line.to_string());
+            }
+        }
+    }
+
+    Ok(values)
+
+}
+
+/// Get the table name of a TOML header line like `[package.metadata]`, `[]` for arrays of tables. `None` if line isn't a header.
+fn parse_header(line: &str) -> Option<String> {
+
+    let header = line.strip_prefix('[')?;
+
+    Some(match header.split_once(']') {
+        Some((name, _)) if !name.starts_with('[') => name.split('.').map(|part| part.trim().trim_matches('"')).collect::<Vec<&str>>().join("."),
+        _ => String::from("[]"),
+    })
+
+}
+
+/// Get the raw value of a key of a table from TOML content, with the following lines of a multi-line array.
+/// Other keys of the table, like dotted keys, are ignored. `None` if key isn't set.
+fn find_value(content: &str, table: &str, key: &str) -> Option<String> {
+
+    let mut current = String::new();        // Current table name
+    let mut lines = content.lines();
+
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+
+        if let Some(name) = parse_header(line) {
+            current = name;
+            continue;
+        }
+
+        let Some((line_key, value)) = line.split_once(TOML_ASSIGN) else {
+            continue;
+        };
+
+        if current == table && line_key.trim().trim_matches('"').trim_matches('\'') == key {
+            let mut value = value.trim().to_string();
+            if value.starts_with('[') {
+                // Array ends at the first `]` outside of strings, since paths arrays aren't nested
+                while !parse_string_array(&value).1 {
+                    value.push('\n');
+                    value.push_str(lines.next()?);
+                }
+            }
+            return Some(value);
+        }
+    }
+
+    None
+
+}
+
+/// Parse strings of a TOML array of strings, like `["ponyo", 'crates/*']`, which can span many lines with comments.
+///
+/// Returns the strings found and true if the array is closed.
+fn parse_string_array(value: &str) -> (Vec<String>, bool) {
+
+    let mut strings : Vec<String> = Vec::new();
+    let mut chars = value.chars().skip(1);
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => strings.push(chars.by_ref().take_while(|end| *end != c).collect()),
+            TOML_COMMENT => { chars.by_ref().take_while(|end| *end != '\n').for_each(drop); },
+            ']' => return (strings, true),
+            _ => {},
+        }
+    }
+
+    (strings, false)
+
+}
+
+/// Returns true if the package at member path, relative to the workspace root, is a member of the workspace of TOML content,
+/// false if it isn't, or `None` if it is excluded.
+///
+/// Members are listed in `members`, with glob patterns like `crates/*`, or are path dependencies of the root package.
+/// Paths in `exclude` exclude the folders they contain, even from glob patterns, unless a path of `members` contains them.
+fn is_workspace_member(content: &str, member: &str) -> Option<bool> {
+
+    let member : Vec<&str> = split_path(member);
+    let get_paths = |key| find_value(content, WORKSPACE_TABLE, key).map(|value| parse_string_array(&value).0).unwrap_or_default();
+
+    let members = get_paths(WORKSPACE_MEMBERS_KEY);
+    let listed = member.is_empty() || members.iter().any(|path| {
+        let path = split_path(path);
+        path.len() == member.len() && path.iter().zip(member.iter()).all(|(pattern, name)| match_pattern(pattern, name))
+    });
+
+    let excluded = get_paths(WORKSPACE_EXCLUDE_KEY).iter().any(|path| member.starts_with(&split_path(path)));
+    if excluded && !members.iter().any(|path| member.starts_with(&split_path(path))) {
+        return None;
+    }
+
+    Some(listed || parse_path_dependencies(content).iter().any(|path| split_path(path) == member))
+
+}
+
+/// Get paths of path dependencies of TOML content, like `ham = { path = "ham" }` in `[dependencies]` or `path = "ham"`
+/// in `[dev-dependencies.ham]`. Dependencies of `[workspace.dependencies]` are only members if a member uses them.
+fn parse_path_dependencies(content: &str) -> Vec<String> {
+
+    let mut paths : Vec<String> = Vec::new();
+    let mut current : Vec<String> = Vec::new();     // Current table name parts
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if let Some(name) = parse_header(line) {
+            current = name.split(TOML_DOTTED_KEY).map(String::from).collect();
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(TOML_ASSIGN).filter(|_| current.first().is_some_and(|table| table != WORKSPACE_TABLE)) else {
+            continue;
+        };
+
+        let path = match current.as_slice() {
+            // `path = "ham"` of `[dependencies.ham]`
+            [.., table, _] if table.ends_with(DEPENDENCIES_TABLE) && key.trim() == DEPENDENCY_PATH_KEY => parse_string_value(value.trim()),
+
+            // `ham = { path = "ham" }` of `[dependencies]`
+            [.., table] if table.ends_with(DEPENDENCIES_TABLE) => value.match_indices(DEPENDENCY_PATH_KEY)
+                .filter(|(index, _)| value[..*index].trim_end().ends_with(['{', ',']))
+                .find_map(|(index, _)| value[index + DEPENDENCY_PATH_KEY.len()..].trim_start().strip_prefix(TOML_ASSIGN))
+                .and_then(|rest| {
+                    let rest = rest.trim_start();
+                    let quote = rest.chars().next().filter(|quote| matches!(quote, '"' | '\''))?;
+                    rest[1..].split_once(quote).map(|(path, _)| path.to_string())
+                }),
+
+            _ => None,
+        };
+
+        paths.extend(path);
+    }
+
+    paths
+
+}
+
+/// Split a path in its folders names, ignoring `.` and separators at the ends.
+fn split_path(path: &str) -> Vec<&str> {
+
+    path.split(['/', '\\']).filter(|part| !part.is_empty() && *part != ".").collect()
+
+}
+
+/// Returns true if a folder name matches a glob pattern with `*` and `?`.
+fn match_pattern(pattern: &str, name: &str) -> bool {
+
+    match pattern.chars().next() {
+        Some('*') => (0..=name.len()).filter(|index| name.is_char_boundary(*index)).any(|index| match_pattern(&pattern[1..], &name[index..])),
+        Some('?') => name.chars().next().is_some_and(|c| match_pattern(&pattern[1..], &name[c.len_utf8()..])),
+        Some(c) => name.starts_with(c) && match_pattern(&pattern[c.len_utf8()..], &name[c.len_utf8()..]),
+        None => name.is_empty(),
+    }
+
+}
+
+/// Returns true if TOML content has table or one of its sub tables, like `[workspace]` or `[workspace.metadata]`.
+pub fn has_table(content: &str, table: &str) -> bool {
+
+    content.lines()
+        .filter_map(|line| line.trim().strip_prefix('[')?.split_once(']'))
+        .map(|(name, _)| name.split('.').map(|part| part.trim().trim_matches('"')).collect::<Vec<&str>>())
+        .any(|name| name.first() == Some(&table))
+
+}
+
+/// Parse a TOML string value followed by an optional comment.
+fn parse_string_value(value: &str) -> Option<String> {
+
+    match value.chars().next()? {
+        // Literal string has no escapes
+        '\'' => {
+            let (literal, rest) = value[1..].split_once('\'')?;
+            is_comment(rest).then(|| literal.to_string())
+        },
+
+        // Basic string ends at first unescaped `"`
+        '"' => {
+            let mut escaped = false;
+            let end = value.char_indices().skip(1).find(|(_, c)| {
+                let end = *c == '"' && !escaped;
+                escaped = *c == '\\' && !escaped;
+                end
+            })?.0;
+            match is_comment(&value[end + 1..]) {
+                true => decode_basic_string(&value[1..end]),
+                false => None,
+            }
+        },
+
+        _ => None,
+    }
+
+}
+
+/// Decode escapes of a TOML basic string content, like `\"` or `\u00E9`.
+///
+/// Returns `None` if an escape is unknown.
+fn decode_basic_string(content: &str) -> Option<String> {
+
+    let mut decoded = String::new();
+    let mut chars = content.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+
+        decoded.push(match chars.next()? {
+            'b' => '\u{8}',
+            't' => '\t',
+            'n' => '\n',
+            'f' => '\u{c}',
+            'r' => '\r',
+            '"' => '"',
+            '\\' => '\\',
+            'u' => char::from_u32(u32::from_str_radix(&chars.by_ref().take(4).collect::<String>(), 16).ok()?)?,
+            'U' => char::from_u32(u32::from_str_radix(&chars.by_ref().take(8).collect::<String>(), 16).ok()?)?,
+            _ => return None,
+        });
+    }
+
+    Some(decoded)
+
+}
+
+/// Returns true if rest of line is empty or a comment.
+fn is_comment(rest: &str) -> bool {
+
+    let rest = rest.trim();
+    rest.is_empty() || rest.starts_with(TOML_COMMENT)
+
+}
+
+/// Get relative path from folder to path, separated by `/`.
+///
+/// Both are canonicalized when they exist so symbolic links don't change result. Returns `None` if they
+/// don't share a root, like paths on different drives.
+pub fn relative_path(folder: &Path, path: &Path) -> Option<String> {
+
+    let folder = folder.canonicalize().unwrap_or_else(|_| folder.to_path_buf());
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    let folder : Vec<Component> = folder.components().collect();
+    let path : Vec<Component> = path.components().collect();
+
+    let common = folder.iter().zip(path.iter()).take_while(|(a, b)| a == b).count();
+    if common == 0 {
+        return None;
+    }
+
+    let parents = folder[common..].iter().map(|_| String::from(".."));
+    let children = path[common..].iter().map(|component| component.as_os_str().to_string_lossy().to_string());
+
+    Some(parents.chain(children).collect::<Vec<String>>().join("/"))
+
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{parse_table, has_table, relative_path, find_value, is_workspace_member};
+
+    /// Test string values of tables are parsed and others ignored
+    #[test]
+    fn parse_table_values() {
+
+        let content = r#"
+            folder = "root/unit"
+
+            [package]
+            name = "ponyo"
+
+            [package.metadata.tests_bin]
+            folder = "tests/ham" # Comment
+            cfg = 'all(test, feature = "slow")'
+            "bench-folder" = "tests/\"bench\""
+            count = 3
+
+            [package.metadata.other]
+            prelude = "use ponyo::*;"
+        "#;
+
+        let values = parse_table(content, "package.metadata.tests_bin").unwrap();
+        assert_eq!(values.get("folder").unwrap(), "tests/ham", "Comment should be removed!");
+        assert_eq!(values.get("cfg").unwrap(), r#"all(test, feature = "slow")"#, "Literal string should keep quotes!");
+        assert_eq!(values.get("bench-folder").unwrap(), r#"tests/"bench""#, "Quoted key and escapes should be decoded!");
+        assert!(!values.contains_key("count"), "Values that aren't strings should be ignored!");
+        assert!(!values.contains_key("prelude"), "Other tables should be ignored!");
+
+        let values = parse_table(content, "").unwrap();
+        assert_eq!(values.get("folder").unwrap(), "root/unit", "Root table values should be parsed!");
+        assert_eq!(values.len(), 1, "Root table should only contain its values!");
+
+    }
+
+    /// Test inline tables, dotted keys and multi-line strings of the table are rejected
+    #[test]
+    fn parse_table_unsupported() {
+
+        let table = "package.metadata.tests_bin";
+        assert_eq!(parse_table("[package.metadata]\ntests_bin = { folder = \"ham\" }", table).unwrap_err(), "tests_bin = { folder = \"ham\" }", "Inline table should be rejected!");
+        assert_eq!(parse_table("[package]\nmetadata.tests_bin.folder = \"ham\"", table).unwrap_err(), "metadata.tests_bin.folder = \"ham\"", "Dotted keys of parent table should be rejected!");
+        assert_eq!(parse_table("[package.metadata.tests_bin]\nbench.folder = \"ham\"", table).unwrap_err(), "bench.folder = \"ham\"", "Dotted keys of table should be rejected!");
+        assert_eq!(parse_table("[package.metadata.tests_bin]\nprelude = \"\"\"", table).unwrap_err(), "prelude = \"\"\"", "Multi-line strings should be rejected!");
+        assert!(parse_table("[package.metadata]\nother = { folder = \"ham\" }\n[package.metadata.tests_bin]\n\"bench.folder\" = \"ham\"", table).is_ok(), "Other keys and quoted keys should be accepted!");
+
+    }
+
+    /// Test workspace table detection
+    #[test]
+    fn has_table_workspace() {
+
+        assert!(has_table("[workspace]\nmembers = [\"ponyo\"]", "workspace"), "`[workspace]` should be found!");
+        assert!(has_table("[package]\n[workspace.metadata.tests_bin]", "workspace"), "Sub table should be found!");
+        assert!(!has_table("[package]\nworkspace = \"..\"", "workspace"), "Key should not be a table!");
+        assert!(!has_table("[workspaces]", "workspace"), "Other table should not be found!");
+
+    }
+
+    /// Test values of keys are found, with multi-line arrays
+    #[test]
+    fn find_value_keys() {
+
+        let content = "[package]\nversion.workspace = true\nworkspace = \"..\" # Root\n\n[workspace]\nmembers = [\n    \"ponyo\", # First\n    'crates/*',\n]\n";
+        assert_eq!(find_value(content, "package", "workspace").unwrap(), "\"..\" # Root", "Value should be found after dotted keys!");
+        assert_eq!(find_value(content, "workspace", "members").unwrap(), "[\n    \"ponyo\", # First\n    'crates/*',\n]", "Array should span lines!");
+        assert!(find_value(content, "workspace", "exclude").is_none(), "Missing key should be None!");
+
+    }
+
+    /// Test workspace members are listed, path dependencies or excluded
+    #[test]
+    fn is_workspace_member_paths() {
+
+        let content = "[workspace]\nmembers = [\"ponyo\", \"crates/*\", \"tools/ham\"]\nexclude = [\"tools\", \"crates/old\"]\n\n[dependencies]\nsosuke = { version = \"1\", path = \"./sosuke\" }\n\n[workspace.dependencies]\nfujimoto = { path = \"fujimoto\" }\n";
+        assert_eq!(is_workspace_member(content, ""), Some(true), "Root package should be a member!");
+        assert_eq!(is_workspace_member(content, "ponyo"), Some(true), "Listed package should be a member!");
+        assert_eq!(is_workspace_member(content, "crates/lisa"), Some(true), "Package matching glob should be a member!");
+        assert_eq!(is_workspace_member(content, "sosuke"), Some(true), "Path dependency should be a member!");
+        assert_eq!(is_workspace_member(content, "tools/ham"), Some(true), "Listed package should not be excluded!");
+        assert_eq!(is_workspace_member(content, "tools/koichi"), None, "Package in excluded folder should be excluded!");
+        assert_eq!(is_workspace_member(content, "crates/old"), None, "Exclusion should override glob members!");
+        assert_eq!(is_workspace_member(content, "fujimoto"), Some(false), "Workspace dependency should not be a member!");
+        assert_eq!(is_workspace_member(content, "crates/lisa/koichi"), Some(false), "Nested package should not be a member!");
+
+    }
+
+    /// Test relative paths between folders and files
+    #[test]
+    fn relative_path_values() {
+
+        assert_eq!(relative_path(Path::new("/ponyo/src"), Path::new("/ponyo/tests/unit/ham.rs")).unwrap(), "../tests/unit/ham.rs", "Expected parent folder then path!");
+        assert_eq!(relative_path(Path::new("/ponyo"), Path::new("/ponyo/tests/unit/ham.rs")).unwrap(), "tests/unit/ham.rs", "Expected path without parent folder!");
+        assert_eq!(relative_path(Path::new("/ponyo/src/bin/sosuke"), Path::new("/ponyo/tests/ham.rs")).unwrap(), "../../../tests/ham.rs", "Expected a parent for each folder!");
+        assert!(relative_path(Path::new("ponyo/src"), Path::new("/ponyo/tests/ham.rs")).is_none(), "Paths without common root should not be relative!");
+
+    }
+
+}